@@ -9,14 +9,14 @@ use silkenweb::{
     clone,
     elements::{
         html::{
-            a, button, div, footer, h1, header, input, label, li, section, span, strong, ul,
+            button, div, footer, h1, header, input, label, li, section, span, strong, ul,
             Button, Div, Footer, Input, Li, Section, Ul,
         },
         ElementEvents, HtmlElement, HtmlElementEvents,
     },
     node::{element::Element, Node},
     prelude::ParentElement,
-    router::url_path,
+    router::{anchor, url_path},
     value::Sig,
 };
 use silkenweb_signals_ext::SignalProduct;
@@ -131,11 +131,11 @@ impl TodoAppView {
         let filter_name = format!("{filter}");
 
         li().child(
-            a().classes(Sig(
-                item_filter.map(move |f| (filter == f).then_some("selected"))
-            ))
-            .href(format!("#/{}", filter_name.to_lowercase()))
-            .text(&filter_name),
+            anchor(format!("#/{}", filter_name.to_lowercase()))
+                .classes(Sig(
+                    item_filter.map(move |f| (filter == f).then_some("selected"))
+                ))
+                .text(&filter_name),
         )
         .text(seperator)
     }
@@ -1,10 +1,16 @@
 use std::borrow::Cow;
 
+use futures_signals::signal::Mutable;
 use parse_display::Display;
 use silkenweb::{
     attribute::{AsAttribute, Attribute},
-    custom_html_element, parent_element, Value,
+    custom_html_element,
+    dom::Dom,
+    parent_element,
+    value::Sig,
+    Value,
 };
+use wasm_bindgen::JsCast;
 
 #[derive(Copy, Clone, Eq, PartialEq, Display, Value)]
 pub enum ButtonDesign {
@@ -44,3 +50,64 @@ custom_html_element!(
 );
 
 parent_element!(ui5_button);
+
+custom_html_element!(
+    ui5_input = {
+        dom_type: web_sys::HtmlElement;
+        attributes {
+            accessible_name: String,
+            accessible_name_ref: String,
+            disabled: bool,
+            placeholder: String,
+            readonly: bool,
+            required: bool,
+            show_clear_icon: bool,
+            value: String,
+        };
+
+        custom_events {
+            change: web_sys::CustomEvent,
+            input: web_sys::CustomEvent,
+        };
+    }
+);
+
+impl<D: Dom> Ui5Input<D> {
+    /// Bind the input's value to `value`, in both directions.
+    ///
+    /// The `value` attribute is set from `value`, and `value` is set when the
+    /// user types into the input. Updates from the input use `set_neq`, so
+    /// the signal doesn't yield the value the input already has. That way,
+    /// the `value` attribute isn't set again while the user is typing, which
+    /// could move the cursor.
+    pub fn bind_value(self, value: &Mutable<String>) -> Self {
+        self.value(Sig(value.signal_cloned())).on_input({
+            let value = value.clone();
+
+            move |_, input| {
+                // `ui5-input` has a `value` property, like `<input>`.
+                value.set_neq(input.unchecked_into::<web_sys::HtmlInputElement>().value())
+            }
+        })
+    }
+}
+
+custom_html_element!(
+    ui5_dialog = {
+        dom_type: web_sys::HtmlElement;
+        attributes {
+            accessible_name: String,
+            header_text: String,
+            open: bool,
+            resizable: bool,
+            stretch: bool,
+        };
+
+        custom_events {
+            before_close: web_sys::CustomEvent,
+            close: web_sys::CustomEvent,
+        };
+    }
+);
+
+parent_element!(ui5_dialog);
@@ -1,6 +1,6 @@
 use futures_signals::signal::{Mutable, SignalExt};
-use silkenweb::{elements::html::*, log_panics, prelude::*, value::Sig};
-use silkenweb_example_web_components_wrapper::{ui5_button, ButtonDesign};
+use silkenweb::{clone, elements::html::*, log_panics, prelude::*, value::Sig};
+use silkenweb_example_web_components_wrapper::{ui5_button, ui5_dialog, ui5_input, ButtonDesign};
 
 fn main() {
     log_panics();
@@ -11,6 +11,10 @@ fn main() {
         count.replace_with(|i| *i + 1);
     };
 
+    let name = Mutable::new(String::new());
+    let greeting = name.signal_cloned().map(|name| format!("Hello, {name}!"));
+    let dialog_open = Mutable::new(false);
+
     let app = div()
         .child(
             ui5_button()
@@ -18,7 +22,29 @@ fn main() {
                 .text("+")
                 .on_click(inc),
         )
-        .child(p().text(Sig(count_text)));
+        .child(p().text(Sig(count_text)))
+        .child(ui5_input().placeholder("Name").bind_value(&name))
+        .child(ui5_button().text("Greet").on_click({
+            clone!(dialog_open);
+            move |_, _| dialog_open.set(true)
+        }))
+        .child(
+            ui5_dialog()
+                .header_text("Greeting")
+                .open(Sig(dialog_open.signal()))
+                .on_close({
+                    clone!(dialog_open);
+                    move |_, _| dialog_open.set(false)
+                })
+                .child(p().text(Sig(greeting)))
+                .child_in_slot(
+                    "footer",
+                    ui5_button()
+                        .design(ButtonDesign::Emphasized)
+                        .text("Close")
+                        .on_click(move |_, _| dialog_open.set(false)),
+                ),
+        );
 
     mount("app", app);
 }
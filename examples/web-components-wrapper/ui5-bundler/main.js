@@ -1 +1,3 @@
 import "@ui5/webcomponents/dist/Button";
+import "@ui5/webcomponents/dist/Dialog";
+import "@ui5/webcomponents/dist/Input";
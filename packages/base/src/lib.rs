@@ -36,10 +36,22 @@ pub mod window {
     pub fn performance() -> Option<web_sys::Performance> {
         WINDOW.with(|w| w.performance())
     }
+
+    pub fn match_media(query: &str) -> Result<Option<web_sys::MediaQueryList>, JsValue> {
+        WINDOW.with(|w| w.match_media(query))
+    }
+
+    pub fn geolocation() -> Result<web_sys::Geolocation, JsValue> {
+        WINDOW.with(|w| w.navigator().geolocation())
+    }
+
+    pub fn indexed_db() -> Result<Option<web_sys::IdbFactory>, JsValue> {
+        WINDOW.with(|w| w.indexed_db())
+    }
 }
 
 pub mod document {
-    use wasm_bindgen::{JsValue, UnwrapThrowExt};
+    use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
     use super::DOCUMENT;
 
@@ -77,6 +89,30 @@ pub mod document {
     pub fn body() -> Option<web_sys::HtmlElement> {
         DOCUMENT.with(|doc| doc.body())
     }
+
+    pub fn set_title(title: &str) {
+        DOCUMENT.with(|doc| doc.set_title(title))
+    }
+
+    /// Call `document.startViewTransition`, if it's supported.
+    ///
+    /// This returns the transition's `finished` promise, or `None` if view
+    /// transitions aren't supported.
+    pub fn start_view_transition(update: &js_sys::Function) -> Option<js_sys::Promise> {
+        DOCUMENT.with(|doc| {
+            let start_view_transition: js_sys::Function =
+                js_sys::Reflect::get(doc, &"startViewTransition".into())
+                    .ok()?
+                    .dyn_into()
+                    .ok()?;
+            let transition = start_view_transition.call1(doc, update).unwrap_throw();
+
+            js_sys::Reflect::get(&transition, &"finished".into())
+                .unwrap_throw()
+                .dyn_into()
+                .ok()
+        })
+    }
 }
 
 pub trait GlobalEventTarget {
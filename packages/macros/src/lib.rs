@@ -7,11 +7,12 @@ use quote::quote;
 use silkenweb_base::css::{self, Source};
 use syn::{
     parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed,
-    FieldsUnnamed, Ident, Index, LitBool,
+    FieldsUnnamed, Ident, Index, LitBool, LitStr,
 };
 
 use crate::parse::Input;
 
+mod manifest;
 mod parse;
 
 macro_rules! derive_empty(
@@ -170,9 +171,26 @@ pub fn derive_element(item: TokenStream) -> TokenStream {
                 Self{#target: self.#target.spawn_future(future) #other_fields}
             }
 
+            fn keep_alive(self, resource: impl 'static) -> Self {
+                Self{#target: self.#target.keep_alive(resource) #other_fields}
+            }
+
+            fn shrink_to_fit(self) -> Self {
+                Self{#target: self.#target.shrink_to_fit() #other_fields}
+            }
+
             fn on(self, name: &'static str, f: impl FnMut(::silkenweb::macros::JsValue) + 'static) -> Self {
                 Self{#target: self.#target.on(name, f) #other_fields}
             }
+
+            fn on_with_options(
+                self,
+                name: &'static str,
+                options: ::silkenweb::node::element::EventListenerOptions,
+                f: impl FnMut(::silkenweb::macros::JsValue) + 'static,
+            ) -> Self {
+                Self{#target: self.#target.on_with_options(name, options, f) #other_fields}
+            }
         }
     )
     .into()
@@ -450,6 +468,16 @@ fn define_css_entity(ident: String, name: String, auto_mount: bool) -> proc_macr
     }
 }
 
+#[proc_macro]
+#[proc_macro_error]
+pub fn custom_elements_manifest(input: TokenStream) -> TokenStream {
+    let path: LitStr = parse_macro_input!(input);
+
+    manifest::code_gen(&path)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// Convert a rust ident to an html ident by stripping any "r#" prefix and
 /// replacing '_' with '-'.
 #[doc(hidden)]
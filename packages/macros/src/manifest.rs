@@ -0,0 +1,262 @@
+//! Generate element wrappers from a [Custom Elements Manifest].
+//!
+//! [Custom Elements Manifest]: https://github.com/webcomponents/custom-elements-manifest
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::abort_call_site;
+use quote::{format_ident, quote};
+use serde_json::Value;
+use syn::{Ident, LitStr};
+
+pub fn code_gen(path_lit: &LitStr) -> syn::Result<TokenStream> {
+    const CARGO_MANIFEST_DIR: &str = "CARGO_MANIFEST_DIR";
+
+    let root_dir = env::var(CARGO_MANIFEST_DIR).unwrap_or_else(|e| {
+        abort_call_site!(
+            "Error reading environment variable '{}': {}",
+            CARGO_MANIFEST_DIR,
+            e
+        )
+    });
+    let path = PathBuf::from(root_dir)
+        .join(path_lit.value())
+        .into_os_string()
+        .into_string()
+        .expect("Expected path to be convertible to string");
+    let content = fs::read_to_string(&path)
+        .unwrap_or_else(|e| abort_call_site!("Failed to read '{}': {}", path, e));
+    let manifest: Value = serde_json::from_str(&content)
+        .unwrap_or_else(|e| abort_call_site!("Failed to parse '{}': {}", path, e));
+
+    let declarations = manifest["modules"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|module| module["declarations"].as_array().into_iter().flatten())
+        .filter(|declaration| declaration["customElement"].as_bool() == Some(true));
+    let mut tags = HashMap::new();
+    let mut elements = Vec::new();
+
+    for declaration in declarations {
+        if let Some(tag) = declaration["tagName"].as_str() {
+            let name = element_ident(tag, path_lit, &mut tags)?;
+            elements.push(element(declaration, tag, name));
+        }
+    }
+
+    Ok(quote!(
+        const _: &[u8] = ::std::include_bytes!(#path);
+
+        #(#elements)*
+    ))
+}
+
+/// The Rust name for the element `tag`.
+///
+/// Element names can't be raw identifiers, so keywords are an error, as are
+/// two tags that map to the same name. `tags` maps each name to the first tag
+/// that used it.
+fn element_ident<'a>(
+    tag: &'a str,
+    path_lit: &LitStr,
+    tags: &mut HashMap<String, &'a str>,
+) -> syn::Result<Ident> {
+    let name = snake_case(tag);
+    let ident = syn::parse_str::<Ident>(&name).map_err(|_| {
+        syn::Error::new(
+            path_lit.span(),
+            format!("Tag `{tag}` maps to `{name}`, which isn't a valid Rust identifier"),
+        )
+    })?;
+
+    if let Some(existing) = tags.insert(name.clone(), tag) {
+        return Err(syn::Error::new(
+            path_lit.span(),
+            format!("Tags `{existing}` and `{tag}` both map to `{name}`"),
+        ));
+    }
+
+    Ok(ident)
+}
+
+fn element(declaration: &Value, tag: &str, name: Ident) -> TokenStream {
+    let camel_name = camel_case(tag);
+    let doc = doc(declaration);
+
+    let mut enums = Vec::new();
+    let attributes = declaration["attributes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|attribute| {
+            let text_name = attribute["name"].as_str()?;
+            let ident = rust_ident(&snake_case(text_name))?;
+            let doc = doc(attribute);
+            let typ = match attribute_type(attribute) {
+                AttributeType::Bool => quote!(bool),
+                AttributeType::Number => quote!(f64),
+                AttributeType::String => quote!(String),
+                AttributeType::Enum(variants) => {
+                    let enum_name = format_ident!("{}{}", camel_name, camel_case(text_name));
+                    enums.push(define_enum(&enum_name, &variants));
+                    quote!(#enum_name)
+                }
+            };
+
+            Some(quote!(#doc #ident(#text_name): #typ))
+        })
+        .collect::<Vec<_>>();
+
+    let events = declaration["events"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|event| {
+            let name = event["name"].as_str()?;
+            let ident = snake_case(name);
+
+            // `custom_events` derives the event name from the ident, by
+            // replacing `_` with `-`, so skip any events it can't name.
+            if ident.replace('_', "-") != name {
+                return None;
+            }
+
+            let ident = rust_ident(&ident)?;
+            let doc = doc(event);
+
+            Some(quote!(#doc #ident: ::silkenweb::macros::web_sys::CustomEvent))
+        });
+
+    quote!(
+        #(#enums)*
+
+        ::silkenweb::custom_html_element!(
+            #doc
+            #name(#tag) = {
+                dom_type: ::silkenweb::macros::web_sys::HtmlElement;
+                attributes { #(#attributes),* };
+                custom_events { #(#events),* };
+            }
+        );
+
+        ::silkenweb::parent_element!(#name);
+    )
+}
+
+fn doc(item: &Value) -> TokenStream {
+    let doc = item["description"]
+        .as_str()
+        .or_else(|| item["summary"].as_str())
+        .into_iter()
+        .flat_map(str::lines);
+
+    quote!(#(#[doc = #doc])*)
+}
+
+enum AttributeType {
+    Bool,
+    Number,
+    String,
+    Enum(Vec<String>),
+}
+
+/// Map a manifest type, such as `boolean | undefined` or `"A" | "B"`, to a
+/// Rust type.
+fn attribute_type(attribute: &Value) -> AttributeType {
+    let text = attribute["type"]["text"].as_str().unwrap_or("string");
+    let alternatives: Vec<&str> = text
+        .split('|')
+        .map(str::trim)
+        .filter(|alternative| !matches!(*alternative, "undefined" | "null"))
+        .collect();
+
+    match alternatives.as_slice() {
+        ["boolean"] => AttributeType::Bool,
+        ["number"] => AttributeType::Number,
+        [] => AttributeType::String,
+        alternatives => alternatives
+            .iter()
+            .map(|alternative| {
+                let variant = alternative
+                    .strip_prefix('"')
+                    .and_then(|variant| variant.strip_suffix('"'))
+                    .or_else(|| {
+                        alternative
+                            .strip_prefix('\'')
+                            .and_then(|variant| variant.strip_suffix('\''))
+                    })?;
+
+                rust_ident(&camel_case(variant)).map(|_| variant.to_string())
+            })
+            .collect::<Option<Vec<_>>>()
+            .map_or(AttributeType::String, AttributeType::Enum),
+    }
+}
+
+fn define_enum(name: &Ident, variants: &[String]) -> TokenStream {
+    let idents = variants
+        .iter()
+        .map(|variant| Ident::new(&camel_case(variant), Span::call_site()));
+    let variant_idents = idents.clone();
+
+    quote!(
+        #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+        pub enum #name {
+            #(#idents),*
+        }
+
+        impl ::silkenweb::attribute::Attribute for #name {
+            fn text(&self) -> ::std::option::Option<::std::borrow::Cow<str>> {
+                ::std::option::Option::Some(::std::borrow::Cow::Borrowed(match self {
+                    #(Self::#variant_idents => #variants),*
+                }))
+            }
+        }
+
+        impl ::silkenweb::attribute::AsAttribute<#name> for #name {}
+
+        impl ::silkenweb::value::Value for #name {}
+    )
+}
+
+/// Parse `name` as an identifier, using a raw identifier for keywords.
+///
+/// Returns `None` if `name` isn't a valid identifier.
+fn rust_ident(name: &str) -> Option<Ident> {
+    syn::parse_str(name)
+        .or_else(|_| syn::parse_str(&format!("r#{name}")))
+        .ok()
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+
+    for (index, c) in name.char_indices() {
+        if c.is_ascii_uppercase() {
+            if index != 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+
+            snake.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            snake.push(c);
+        } else if !snake.ends_with('_') {
+            snake.push('_');
+        }
+    }
+
+    snake
+}
+
+fn camel_case(name: &str) -> String {
+    snake_case(name)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
@@ -8,18 +8,8 @@
 //! ```
 //! # use futures_signals::signal::Mutable;
 //! # use silkenweb_signals_ext::value::*;
-//! # use std::future::Future;
 //! #
-//! struct Exec;
-//!
-//! impl Executor for Exec {
-//!     fn spawn(&mut self, future: impl Future<Output = ()> + 'static) {
-//!         // This is a stub `Executor` for brevity. In real code, it should
-//!         // run the future.
-//!     }
-//! }
-//!
-//! fn increment_and_print(x: impl SignalOrValue<Item = i32>) {
+//! fn increment_and_print(x: impl SignalOrValue<Item = i32>, exec: &mut TestExecutor) {
 //!     x.map(|x| x + 1).for_each(
 //!         |_exec, x| println!("{x}"),
 //!         |_exec| {
@@ -30,25 +20,57 @@
 //!                 async {}
 //!             }
 //!         },
-//!         &mut Exec,
+//!         exec,
 //!     );
 //! }
 //!
+//! let mut exec = TestExecutor::new();
 //! let x_signal = Mutable::new(0);
 //! let x_value = 0;
 //!
-//! increment_and_print(x_value);
-//! increment_and_print(Sig(x_signal.signal()));
+//! increment_and_print(x_value, &mut exec);
+//! increment_and_print(Sig(x_signal.signal()), &mut exec);
+//!
+//! x_signal.set(1);
+//! exec.run_until_stalled();
 //! ```
-use std::future::Future;
+//!
+//! [`TestExecutor`] is an [`Executor`] for tests that don't run in a browser.
+//! In an app, use an executor that runs futures on the browser's microtask
+//! queue.
+use std::{borrow::Cow, future::Future, rc::Rc, sync::Arc};
 
+use futures::{executor::LocalPool, task::LocalSpawnExt};
 use futures_signals::signal::{self, Always, Signal, SignalExt};
 
+use crate::{Map2, SignalProduct};
+
 /// Newtype wrapper to mark this type as a signal.
 ///
 /// For use with [`SignalOrValue`] and [`RefSignalOrValue`]
 pub struct Sig<T>(pub T);
 
+impl<S: Signal> Sig<S> {
+    /// Skip values that are equal to the previous value.
+    ///
+    /// Signals can yield the same value more than once, for example, when a
+    /// `Mutable` is set to its current value. Each value is applied to the DOM
+    /// by default, so use this to skip unchanged values.
+    ///
+    /// ```
+    /// # use futures_signals::signal::Mutable;
+    /// # use silkenweb_signals_ext::value::*;
+    /// let text = Mutable::new("Hello");
+    /// let text = Sig(text.signal()).dedupe();
+    /// ```
+    pub fn dedupe(self) -> Sig<signal::DedupeCloned<S>>
+    where
+        S::Item: PartialEq + Clone,
+    {
+        Sig(self.0.dedupe_cloned())
+    }
+}
+
 /// Newtype wrapper to mark this type as a static value.
 ///
 /// For use with [`SignalOrValue`] and [`RefSignalOrValue`] for when you can't
@@ -68,12 +90,56 @@ pub trait RefSignalOrValue<'a> {
         F: FnMut(Self::Item) -> R + 'b,
         R: RefSignalOrValue<'b, Item = R> + 'b;
 
+    /// The return type for [`Self::map2`].
+    type Map2<Other, F, R>: RefSignalOrValue<'a, Item = R> + 'a
+    where
+        Other: RefSignalOrValue<'a> + 'a,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'a,
+        R: RefSignalOrValue<'a, Item = R> + 'a;
+
     /// Map a function over this signal/value to produce a new signal/value.
     fn map<'b: 'a, F, R>(self, callback: F) -> Self::Map<'b, F, R>
     where
         R: RefSignalOrValue<'b, Item = R> + 'b,
         F: FnMut(Self::Item) -> R + 'b;
 
+    /// Combine this signal/value with `other` to produce a new signal/value.
+    ///
+    /// If both are values, `callback` is called straight away and the result
+    /// is a value. Otherwise, the result is a signal that yields a new value
+    /// whenever either input changes.
+    ///
+    /// ```
+    /// # use futures::{executor::block_on, StreamExt};
+    /// # use futures_signals::signal::{Mutable, SignalExt};
+    /// # use silkenweb_signals_ext::value::*;
+    /// let fraction = |x: &i32, y: &i32| format!("{x}/{y}");
+    /// assert_eq!(1.map2(2, fraction), "1/2");
+    ///
+    /// let x = Mutable::new(1);
+    /// let numerator: Sig<_> = Sig(x.signal()).map2(2, fraction);
+    /// let denominator: Sig<_> = 1.map2(Sig(x.signal()), fraction);
+    /// let mut numerator = numerator.into_signal().to_stream();
+    /// let mut denominator = denominator.into_signal().to_stream();
+    ///
+    /// assert_eq!(block_on(numerator.next()).unwrap(), "1/2");
+    /// assert_eq!(block_on(denominator.next()).unwrap(), "1/1");
+    ///
+    /// x.set(3);
+    /// assert_eq!(block_on(numerator.next()).unwrap(), "3/2");
+    /// assert_eq!(block_on(denominator.next()).unwrap(), "1/3");
+    /// ```
+    fn map2<Other, F, R>(self, other: Other, callback: F) -> Self::Map2<Other, F, R>
+    where
+        Other: RefSignalOrValue<'a> + 'a,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'a,
+        R: RefSignalOrValue<'a, Item = R> + 'a;
+
+    /// Convert this into a signal.
+    ///
+    /// Values are converted with [`signal::always`].
+    fn into_signal(self) -> Self::Signal;
+
     /// Apply a function over the value or each value of a signal.
     ///
     /// # Params
@@ -120,6 +186,64 @@ pub trait Executor {
     fn spawn(&mut self, future: impl Future<Output = ()> + 'static);
 }
 
+/// An [`Executor`] that runs futures on the current thread, for testing.
+///
+/// Futures are polled as soon as they're spawned, so they can process the
+/// initial value of a signal. After that, call
+/// [`run_until_stalled`][Self::run_until_stalled] to process any changes.
+///
+/// # Example
+///
+/// ```
+/// # use std::{cell::RefCell, rc::Rc};
+/// # use futures_signals::signal::Mutable;
+/// # use silkenweb_signals_ext::value::*;
+/// let values = Rc::new(RefCell::new(Vec::new()));
+/// let x = Mutable::new(0);
+/// let mut exec = TestExecutor::new();
+///
+/// Sig(x.signal()).for_each(
+///     |_exec, _x| unreachable!("`x` is a signal"),
+///     |_exec| {
+///         let values = values.clone();
+///         move |x| {
+///             values.borrow_mut().push(x);
+///             async {}
+///         }
+///     },
+///     &mut exec,
+/// );
+///
+/// assert_eq!(*values.borrow(), [0]);
+///
+/// x.set(1);
+/// exec.run_until_stalled();
+/// assert_eq!(*values.borrow(), [0, 1]);
+/// ```
+#[derive(Default)]
+pub struct TestExecutor(LocalPool);
+
+impl TestExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run spawned futures until none of them can make any more progress.
+    pub fn run_until_stalled(&mut self) {
+        self.0.run_until_stalled()
+    }
+}
+
+impl Executor for TestExecutor {
+    fn spawn(&mut self, future: impl Future<Output = ()> + 'static) {
+        self.0
+            .spawner()
+            .spawn_local(future)
+            .expect("executor should be running");
+        self.run_until_stalled();
+    }
+}
+
 /// Marker trait for values that can be used with [`RefSignalOrValue`].
 pub trait RefValue<'a> {}
 
@@ -136,13 +260,17 @@ macro_rules! static_values{
     }
 }
 
-static_values!(i8, i16, i32, i64);
-static_values!(u8, u16, u32, u64);
+static_values!(i8, i16, i32, i64, i128, isize);
+static_values!(u8, u16, u32, u64, u128, usize);
 static_values!(f32, f64);
-static_values!(bool, String);
+static_values!(bool, char, String, Rc<str>, Arc<str>);
 
 impl<'a> RefValue<'a> for &'a str {}
 impl<'a> RefValue<'a> for &'a String {}
+impl<'a> RefValue<'a> for Cow<'a, str> {}
+// This covers owned values as well, as `Option<T>` is a `RefValue<'static>` for
+// any `T: 'static`. `Option<T>` can't also implement `Value`, as it would
+// overlap with this.
 impl<'a, T: 'a> RefValue<'a> for Option<T> {}
 impl<'a, T: 'a> RefValue<'a> for [T] {}
 impl<'a, T: 'a> RefValue<'a> for &'a [T] {}
@@ -171,6 +299,11 @@ where
         'b: 'a,
         F: FnMut(Self::Item) -> R + 'b,
         R: RefSignalOrValue<'b, Item = R> + 'b;
+    type Map2<Other, F, R> = Other::Map<'a, Box<dyn FnMut(Other::Item) -> R + 'a>, R>
+    where
+        Other: RefSignalOrValue<'a> + 'a,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'a,
+        R: RefSignalOrValue<'a, Item = R> + 'a;
     type Signal = Always<Self::Item>;
 
     fn map<'b: 'a, F, R>(self, mut callback: F) -> Self::Map<'b, F, R>
@@ -181,6 +314,20 @@ where
         callback(self)
     }
 
+    fn map2<Other, F, R>(self, other: Other, mut callback: F) -> Self::Map2<Other, F, R>
+    where
+        Other: RefSignalOrValue<'a> + 'a,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'a,
+        R: RefSignalOrValue<'a, Item = R> + 'a,
+    {
+        let value = self;
+        other.map(Box::new(move |other| callback(&value, &other)))
+    }
+
+    fn into_signal(self) -> Self::Signal {
+        signal::always(self)
+    }
+
     fn for_each<FVal, FInitSig, FSig, Task, Exec>(
         self,
         fn_val: FVal,
@@ -215,6 +362,11 @@ where
         'b: 'a,
         F: FnMut(Self::Item) -> R + 'b,
         R: RefSignalOrValue<'b, Item = R> + 'b;
+    type Map2<Other, F, R> = Other::Map<'a, Box<dyn FnMut(Other::Item) -> R + 'a>, R>
+    where
+        Other: RefSignalOrValue<'a> + 'a,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'a,
+        R: RefSignalOrValue<'a, Item = R> + 'a;
     type Signal = Always<Self::Item>;
 
     fn map<'b: 'a, F, R>(self, mut callback: F) -> Self::Map<'b, F, R>
@@ -225,6 +377,20 @@ where
         callback(self.0)
     }
 
+    fn map2<Other, F, R>(self, other: Other, mut callback: F) -> Self::Map2<Other, F, R>
+    where
+        Other: RefSignalOrValue<'a> + 'a,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'a,
+        R: RefSignalOrValue<'a, Item = R> + 'a,
+    {
+        let value = self.0;
+        other.map(Box::new(move |other| callback(&value, &other)))
+    }
+
+    fn into_signal(self) -> Self::Signal {
+        signal::always(self.0)
+    }
+
     fn for_each<FVal, FInitSig, FSig, Task, Exec>(
         self,
         fn_val: FVal,
@@ -260,6 +426,11 @@ where
         'b: 'static,
         F: FnMut(Self::Item) -> R + 'b,
         R: RefSignalOrValue<'b, Item = R> + 'b;
+    type Map2<Other, F, R> = Sig<Map2<S, Other::Signal, F>>
+    where
+        Other: RefSignalOrValue<'static> + 'static,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'static,
+        R: RefSignalOrValue<'static, Item = R> + 'static;
     type Signal = S;
 
     fn map<'b, F, R>(self, callback: F) -> Self::Map<'b, F, R>
@@ -271,6 +442,19 @@ where
         Sig(self.0.map(callback))
     }
 
+    fn map2<Other, F, R>(self, other: Other, callback: F) -> Self::Map2<Other, F, R>
+    where
+        Other: RefSignalOrValue<'static> + 'static,
+        F: FnMut(&Self::Item, &Other::Item) -> R + 'static,
+        R: RefSignalOrValue<'static, Item = R> + 'static,
+    {
+        Sig((self.0, other.into_signal()).signal_ref(callback))
+    }
+
+    fn into_signal(self) -> Self::Signal {
+        self.0
+    }
+
     fn for_each<FVal, FInitSig, FSig, Task, Exec>(
         self,
         _fn_val: FVal,
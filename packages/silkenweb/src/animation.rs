@@ -6,9 +6,20 @@
 //! time.
 //!
 //! See [`finite_animation`] and [`infinite_animation`] for examples.
+//!
+//! [`throttle_to_frame`] will limit an existing signal to one update per
+//! animation frame.
+use std::{
+    cell::{Cell, RefCell},
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
 use futures_signals::signal::{Signal, SignalExt};
+use pin_project::pin_project;
 
-use crate::task::{animation_timestamp, request_animation_frame};
+use crate::task::{animation_timestamp, on_animation_frame, request_animation_frame};
 
 /// Provide a finite time signal for animations.
 ///
@@ -77,3 +88,117 @@ pub fn infinite_animation() -> impl Signal<Item = f64> + 'static {
         })
         .dedupe()
 }
+
+/// Sample a signal at most once per animation frame.
+///
+/// The first value is yielded immediately. After that, any burst of updates
+/// between animation frames is collapsed into the latest value, which is
+/// yielded on the next animation frame. This is useful for scroll or pointer
+/// driven visuals, where there's no point rendering more than once per frame.
+///
+/// An animation frame is only requested while there's a value waiting, so no
+/// more frames are scheduled once the signal is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// # use html::{div, Div};
+/// # use futures_signals::signal::Mutable;
+/// # use silkenweb::{animation::throttle_to_frame, prelude::*};
+/// let scroll_top = Mutable::new(0.0);
+/// # let d: Div =
+/// div().style_property(
+///     "transform",
+///     Sig(throttle_to_frame(scroll_top.signal()).map(|y| format!("translateY({y}px)"))),
+/// );
+/// ```
+pub fn throttle_to_frame<S: Signal>(signal: S) -> ThrottleToFrame<S> {
+    ThrottleToFrame {
+        signal: Some(signal),
+        latest: None,
+        frame: None,
+        is_first: true,
+    }
+}
+
+/// A signal that yields at most once per animation frame.
+///
+/// See [`throttle_to_frame`].
+#[must_use = "Signals do nothing unless polled"]
+#[pin_project]
+pub struct ThrottleToFrame<S: Signal> {
+    #[pin]
+    signal: Option<S>,
+    latest: Option<S::Item>,
+    frame: Option<Rc<Frame>>,
+    is_first: bool,
+}
+
+impl<S: Signal> Signal for ThrottleToFrame<S> {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut proj = self.project();
+
+        while let Some(signal) = proj.signal.as_mut().as_pin_mut() {
+            match signal.poll_change(cx) {
+                Poll::Ready(Some(value)) => *proj.latest = Some(value),
+                Poll::Ready(None) => proj.signal.set(None),
+                Poll::Pending => break,
+            }
+        }
+
+        if *proj.is_first && proj.latest.is_some() {
+            *proj.is_first = false;
+            return Poll::Ready(proj.latest.take());
+        }
+
+        let frame_ready = proj.frame.as_ref().map(|frame| frame.ready.get());
+
+        match (frame_ready, proj.latest.is_some()) {
+            (Some(true), true) => {
+                *proj.frame = None;
+                return Poll::Ready(proj.latest.take());
+            }
+            (Some(true), false) => *proj.frame = None,
+            (None, true) => *proj.frame = Some(Frame::request()),
+            _ => (),
+        }
+
+        if let Some(frame) = proj.frame {
+            frame.waker.replace(Some(cx.waker().clone()));
+            Poll::Pending
+        } else if proj.signal.is_none() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[derive(Default)]
+struct Frame {
+    ready: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl Frame {
+    fn request() -> Rc<Self> {
+        let frame = Rc::new(Self::default());
+        // Only hold a weak reference, so a dropped signal isn't kept alive until
+        // the next frame.
+        let weak_frame = Rc::downgrade(&frame);
+
+        on_animation_frame(move || {
+            if let Some(frame) = weak_frame.upgrade() {
+                frame.ready.set(true);
+
+                if let Some(waker) = frame.waker.take() {
+                    waker.wake();
+                }
+            }
+        });
+
+        frame
+    }
+}
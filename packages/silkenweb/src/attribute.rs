@@ -11,7 +11,7 @@
 //! own html element using the [`custom_html_element`] macro.
 //!
 //! [`Element::attribute`]: crate::node::element::Element::attribute
-use std::borrow::Cow;
+use std::{borrow::Cow, rc::Rc, sync::Arc};
 
 /// A type that can be used as the value of an attribute.
 ///
@@ -44,10 +44,10 @@ macro_rules! define_attribute_values{
     }
 }
 
-define_attribute_values!(i8, i16, i32, i64);
-define_attribute_values!(u8, u16, u32, u64);
+define_attribute_values!(i8, i16, i32, i64, i128, isize);
+define_attribute_values!(u8, u16, u32, u64, u128, usize);
 define_attribute_values!(f32, f64);
-define_attribute_values!(usize);
+define_attribute_values!(char);
 
 impl Attribute for String {
     fn text(&self) -> Option<Cow<str>> {
@@ -92,3 +92,27 @@ impl<'a> Attribute for &'a String {
 }
 
 impl<'a> AsAttribute<String> for &'a String {}
+
+impl<'a> Attribute for Cow<'a, str> {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(Cow::from(self.as_ref()))
+    }
+}
+
+impl<'a> AsAttribute<String> for Cow<'a, str> {}
+
+impl Attribute for Rc<str> {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(Cow::from(self.as_ref()))
+    }
+}
+
+impl AsAttribute<String> for Rc<str> {}
+
+impl Attribute for Arc<str> {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(Cow::from(self.as_ref()))
+    }
+}
+
+impl AsAttribute<String> for Arc<str> {}
@@ -1,16 +1,25 @@
 //! Document utilities.
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+};
 
+use discard::DiscardOnDrop;
+use futures_signals::{signal::SignalExt, CancelableFutureHandle};
+use html_escape::{encode_double_quoted_attribute, encode_text_minimal};
 use paste::paste;
 use silkenweb_base::document;
+use silkenweb_macros::cfg_browser;
+use silkenweb_signals_ext::value::{RefSignalOrValue, SignalOrValue};
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 use crate::{
-    dom::{Dom, Dry, Wet},
+    dom::{Dom, Dry, InstantiableDom, Wet},
+    elements::html::link,
     event::{bubbling_events, GlobalEventCallback},
     insert_element, mount_point,
-    node::element::{Const, Element, GenericElement, Mut},
-    remove_element, task, ELEMENTS,
+    node::element::{spawn_cancelable_future, Const, Element, GenericElement, Mut},
+    reinsert_element, remove_element, task, ELEMENTS,
 };
 
 /// Manage an event handler.
@@ -48,6 +57,53 @@ pub fn on_dom_content_loaded(f: impl FnMut(web_sys::Event) + 'static) -> EventCa
     EventCallback::new("DOMContentLoaded", f)
 }
 
+/// Handle events from descendants matching `selector`, with one listener on
+/// the document.
+///
+/// When a `name` event bubbles up to the document, `f` is called with the
+/// event and the closest ancestor of the event target, including the target
+/// itself, that matches `selector`. Events that don't come from an element
+/// matching `selector` are ignored. This is much cheaper than adding a
+/// listener to each item in a large list or table.
+///
+/// Only events that bubble can be delegated. For example, `focus`, `blur`,
+/// `mouseenter`, `mouseleave`, `load`, `error` and `scroll` on elements don't
+/// bubble, so handlers for them will never be called. Use `focusin`,
+/// `focusout`, `mouseover` and `mouseout` instead, where possible.
+///
+/// This only has an effect on WASM targets.
+///
+/// # Example
+///
+/// ```no_run
+/// # use futures_signals::signal::Mutable;
+/// # use silkenweb::document::delegate;
+/// let selected = Mutable::new(String::new());
+///
+/// delegate("click", "li.item", move |_ev: web_sys::MouseEvent, item| {
+///     selected.set(item.id())
+/// })
+/// .perpetual();
+/// ```
+pub fn delegate<Event: JsCast>(
+    name: &'static str,
+    selector: &str,
+    mut f: impl FnMut(Event, web_sys::Element) + 'static,
+) -> EventCallback {
+    let selector = selector.to_string();
+
+    EventCallback::new(name, move |event: web_sys::Event| {
+        let matched = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            .and_then(|target| target.closest(&selector).ok().flatten());
+
+        if let Some(matched) = matched {
+            f(event.unchecked_into(), matched);
+        }
+    })
+}
+
 events! {
     fullscreenchange: web_sys::Event,
     fullscreenerror: web_sys::Event,
@@ -68,15 +124,58 @@ events! {
 
 bubbling_events!();
 
+/// Update the DOM inside a [view transition].
+///
+/// `update` is called, and any DOM changes it causes are rendered, inside
+/// `document.startViewTransition`. If the browser doesn't support view
+/// transitions, or we're not on a WASM target, `update` is called and the
+/// changes are rendered as normal.
+///
+/// The returned future resolves when the transition has finished.
+///
+/// # Example
+///
+/// ```no_run
+/// # use silkenweb::{document::view_transition, prelude::*, task::spawn_local};
+/// let page = Mutable::new(0);
+///
+/// spawn_local(view_transition(move || page.set(1)));
+/// ```
+///
+/// [view transition]: https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API
+pub async fn view_transition(update: impl FnOnce() + 'static) {
+    arch::view_transition(update).await
+}
+
 pub trait Document: Dom + Sized {
     /// Mount an element on the document.
     ///
     /// `id` is the id of the mount point element. The element will replace
     /// the mount point. The returned `MountHandle` should usually just be
     /// discarded, but it can be used to restore the mount point if
-    /// required. This can be useful for testing.
+    /// required. This can be useful for testing. Use [`Self::mount_append`]
+    /// to keep the mount point, and add the element as a child of it.
     fn mount(id: &str, element: impl Into<GenericElement<Self, Const>>) -> MountHandle;
 
+    /// Mount an element in place of `target`.
+    ///
+    /// This is the same as [`Self::mount`], but the mount point is given
+    /// directly, so it doesn't need an `id`. This is useful for embedding an
+    /// element in a page that's managed by something else, which only gives
+    /// you a reference to a container element.
+    fn mount_to(
+        target: &web_sys::Element,
+        element: impl Into<GenericElement<Self, Const>>,
+    ) -> MountHandle;
+
+    /// Mount an element as the last child of the element with `id`.
+    ///
+    /// Unlike [`Self::mount`], the element with `id` is kept, along with any
+    /// children it already has, so this can be used to mount in a container
+    /// like `<body>`. Unmounting with the returned [`MountHandle`] only
+    /// removes the appended element.
+    fn mount_append(id: &str, element: impl Into<GenericElement<Self, Const>>) -> MountHandle;
+
     /// Remove all mounted elements.
     ///
     /// Mount points will not be restored. This is useful to ensure a clean
@@ -94,6 +193,39 @@ pub trait Document: Dom + Sized {
     /// - `true` is returned.
     fn mount_in_head(id: &str, element: impl Into<GenericElement<Self, Mut>>) -> bool;
 
+    /// Set the [nonce] for elements added with [`Self::mount_in_head`].
+    ///
+    /// Under a Content Security Policy, `<style>` and `<script>` elements
+    /// need a `nonce` attribute that matches the policy, or they'll be
+    /// blocked. This includes stylesheets mounted by [`css!`]. Once a nonce is
+    /// set, it's added to each element mounted in the head, so it's also
+    /// included in [`Self::head_inner_html`]. Use `None` to stop adding a
+    /// nonce.
+    ///
+    /// On the server, the nonce only applies to the current
+    /// [`server::scope`], so each render can use a different nonce.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::style;
+    /// # use silkenweb::{document::Document, dom::Dry, prelude::*, task::server};
+    /// # server::sync_scope(|| {
+    /// Dry::set_nonce(Some("abc123".to_string()));
+    /// Dry::mount_in_head("my-style", style().text("p {}"));
+    ///
+    /// assert_eq!(
+    ///     Dry::head_inner_html(),
+    ///     r#"<style id="my-style" nonce="abc123">p {}</style>"#
+    /// );
+    /// # })
+    /// ```
+    ///
+    /// [nonce]: https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/nonce
+    /// [`css!`]: crate::css
+    /// [`server::scope`]: crate::task::server::scope
+    fn set_nonce(nonce: Option<String>);
+
     /// Get the inner HTML of `<head>`.
     ///
     /// This only includes elements added with `mount_in_head`. It's useful for
@@ -102,17 +234,233 @@ pub trait Document: Dom + Sized {
     /// so hydration can avoid adding duplicate stylesheets with
     /// [`Self::mount_in_head`].
     fn head_inner_html() -> String;
+
+    /// Render an element as the last child of `target`.
+    ///
+    /// This is useful for modals and tooltips, which often need to be a child
+    /// of `document.body`, rather than wherever they are in the component
+    /// tree. The element is removed when the returned [`PortalHandle`] is
+    /// dropped, or by [`Self::unmount_all`].
+    fn portal(
+        target: &web_sys::Element,
+        element: impl Into<GenericElement<Self, Const>>,
+    ) -> PortalHandle;
+
+    /// Set the document title.
+    ///
+    /// `title` can be a value or a signal. On the server, the latest title is
+    /// included as a `<title>` element in [`Self::head_inner_html`].
+    ///
+    /// Dropping the returned [`TitleHandle`] stops updating the title from the
+    /// signal, but leaves the title as it is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use silkenweb::{
+    /// #     document::Document,
+    /// #     dom::Dry,
+    /// #     prelude::*,
+    /// #     task::{render_now, server},
+    /// # };
+    /// # server::block_on(server::scope(async {
+    /// let page = Mutable::new("Home");
+    /// let _title = Dry::set_title(Sig(page.signal()));
+    ///
+    /// render_now().await;
+    /// assert_eq!(Dry::head_inner_html(), "<title>Home</title>");
+    ///
+    /// page.set("About");
+    ///
+    /// render_now().await;
+    /// assert_eq!(Dry::head_inner_html(), "<title>About</title>");
+    /// # }))
+    /// ```
+    fn set_title(title: impl SignalOrValue<Item = impl Into<String> + 'static>) -> TitleHandle;
+
+    /// Set the content of a `<meta name="...">` element in `<head>`.
+    ///
+    /// If there's already a meta element for `name`, its `content` is updated,
+    /// otherwise a new one is added. This includes meta elements from server
+    /// side rendering, so hydration won't duplicate them. `content` can be a
+    /// value or a signal. On the server, meta elements are included in
+    /// [`Self::head_inner_html`].
+    ///
+    /// Dropping the returned [`MetaHandle`] stops updating the content from the
+    /// signal, but leaves the element in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use silkenweb::{document::Document, dom::Dry, task::server};
+    /// # server::sync_scope(|| {
+    /// let _description = Dry::meta("description", "My page");
+    ///
+    /// assert_eq!(
+    ///     Dry::head_inner_html(),
+    ///     r#"<meta id="silkenweb-meta-name-description" name="description" content="My page">"#
+    /// );
+    /// # })
+    /// ```
+    fn meta(
+        name: &str,
+        content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+    ) -> MetaHandle;
+
+    /// Set the content of a `<meta property="...">` element in `<head>`.
+    ///
+    /// This is for [Open Graph] tags, and is otherwise the same as
+    /// [`Self::meta`].
+    ///
+    /// [Open Graph]: https://ogp.me/
+    fn meta_property(
+        property: &str,
+        content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+    ) -> MetaHandle;
+
+    /// Add a `<link rel="preload">` to `<head>`, so the browser fetches
+    /// `href` as soon as possible.
+    ///
+    /// The link is added with [`Self::mount_in_head`], using an `id` based on
+    /// `href`, so each resource is only preloaded once. This includes links
+    /// from server side rendering. Fonts and fetches are preloaded with
+    /// `crossorigin="anonymous"`, as browsers won't use them otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use silkenweb::{
+    /// #     document::{Document, PreloadAs},
+    /// #     dom::Dry,
+    /// #     task::server,
+    /// # };
+    /// # server::sync_scope(|| {
+    /// Dry::preload("/app.css", PreloadAs::Style);
+    ///
+    /// assert_eq!(
+    ///     Dry::head_inner_html(),
+    ///     r#"<link rel="preload" href="/app.css" as="style" id="silkenweb-preload-/app.css">"#
+    /// );
+    /// # })
+    /// ```
+    fn preload(href: &str, as_: PreloadAs) -> bool
+    where
+        Self: InstantiableDom,
+    {
+        let link = link().rel("preload").href(href).r#as(as_.as_str());
+        let link = if as_.is_cors() {
+            link.crossorigin("anonymous")
+        } else {
+            link
+        };
+
+        Self::mount_in_head(&resource_hint_id("preload", href), link)
+    }
+
+    /// Add a `<link rel="prefetch">` to `<head>`, so the browser fetches
+    /// `href` when it's idle.
+    ///
+    /// This is for resources that will probably be needed by the next page.
+    /// Like [`Self::preload`], each resource is only prefetched once.
+    fn prefetch(href: &str) -> bool
+    where
+        Self: InstantiableDom,
+    {
+        Self::mount_in_head(
+            &resource_hint_id("prefetch", href),
+            link().rel("prefetch").href(href),
+        )
+    }
+
+    /// Add a `<link rel="preconnect">` to `<head>`, so the browser connects to
+    /// `origin` before it's needed.
+    ///
+    /// Like [`Self::preload`], each origin is only added once.
+    fn preconnect(origin: &str) -> bool
+    where
+        Self: InstantiableDom,
+    {
+        Self::mount_in_head(
+            &resource_hint_id("preconnect", origin),
+            link().rel("preconnect").href(origin),
+        )
+    }
+}
+
+/// The type of resource to preload with [`Document::preload`].
+///
+/// This is used for the `as` attribute of the `<link>`. See [MDN] for details.
+///
+/// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/rel/preload#what_types_of_content_can_be_preloaded
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PreloadAs {
+    Audio,
+    Document,
+    Embed,
+    Fetch,
+    Font,
+    Image,
+    Object,
+    Script,
+    Style,
+    Track,
+    Video,
+    Worker,
+}
+
+impl PreloadAs {
+    /// The value of the `as` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Audio => "audio",
+            Self::Document => "document",
+            Self::Embed => "embed",
+            Self::Fetch => "fetch",
+            Self::Font => "font",
+            Self::Image => "image",
+            Self::Object => "object",
+            Self::Script => "script",
+            Self::Style => "style",
+            Self::Track => "track",
+            Self::Video => "video",
+            Self::Worker => "worker",
+        }
+    }
+
+    fn is_cors(self) -> bool {
+        matches!(self, Self::Font | Self::Fetch)
+    }
+}
+
+fn resource_hint_id(rel: &str, href: &str) -> String {
+    format!("silkenweb-{rel}-{href}")
 }
 
 impl Document for Wet {
     fn mount(id: &str, element: impl Into<GenericElement<Self, Const>>) -> MountHandle {
+        Self::mount_to(&mount_point(id), element)
+    }
+
+    fn mount_to(
+        target: &web_sys::Element,
+        element: impl Into<GenericElement<Self, Const>>,
+    ) -> MountHandle {
         let element = element.into();
 
-        let mount_point = mount_point(id);
-        mount_point
+        target
             .replace_with_with_node_1(&element.dom_element())
             .unwrap_throw();
-        MountHandle::new(mount_point, element)
+        MountHandle::new(MountPoint::Replaced(target.clone()), element)
+    }
+
+    fn mount_append(id: &str, element: impl Into<GenericElement<Self, Const>>) -> MountHandle {
+        let element = element.into();
+        let parent = mount_point(id);
+
+        parent
+            .append_with_node_1(&element.dom_element())
+            .unwrap_throw();
+        MountHandle::new(MountPoint::Appended(parent), element)
     }
 
     fn unmount_all() {
@@ -135,7 +483,12 @@ impl Document for Wet {
             return false;
         }
 
-        let element = element.into().attribute("id", id).freeze();
+        let nonce = HEAD_NONCE.with(|nonce| nonce.borrow().clone());
+        let element = element
+            .into()
+            .attribute("id", id)
+            .attribute("nonce", nonce)
+            .freeze();
         let dom_element = element.dom_element();
         document::head()
             .map(|head| {
@@ -145,6 +498,10 @@ impl Document for Wet {
             .is_some()
     }
 
+    fn set_nonce(nonce: Option<String>) {
+        HEAD_NONCE.with(|head_nonce| head_nonce.replace(nonce));
+    }
+
     fn head_inner_html() -> String {
         let mut html = String::new();
 
@@ -156,6 +513,60 @@ impl Document for Wet {
 
         html
     }
+
+    fn portal(
+        target: &web_sys::Element,
+        element: impl Into<GenericElement<Self, Const>>,
+    ) -> PortalHandle {
+        let element = element.into();
+        target
+            .append_with_node_1(&element.dom_element())
+            .unwrap_throw();
+        PortalHandle::new(element)
+    }
+
+    fn set_title(title: impl SignalOrValue<Item = impl Into<String> + 'static>) -> TitleHandle {
+        TitleHandle(spawn_setter(title, |title| document::set_title(&title)))
+    }
+
+    fn meta(
+        name: &str,
+        content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+    ) -> MetaHandle {
+        wet_meta("name", name, content)
+    }
+
+    fn meta_property(
+        property: &str,
+        content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+    ) -> MetaHandle {
+        wet_meta("property", property, content)
+    }
+}
+
+fn wet_meta(
+    key_attribute: &'static str,
+    key: &str,
+    content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+) -> MetaHandle {
+    let id = meta_id(key_attribute, key);
+    let element = document::query_selector(&format!("#{}", web_sys::css::escape(&id)))
+        .unwrap_throw()
+        .unwrap_or_else(|| {
+            let element = document::create_element("meta");
+            element.set_id(&id);
+            element.set_attribute(key_attribute, key).unwrap_throw();
+
+            if let Some(head) = document::head() {
+                head.append_with_node_1(&element).unwrap_throw();
+            }
+
+            element
+        });
+
+    MetaHandle(spawn_setter(content, move |content| {
+        element.set_attribute("content", &content).unwrap_throw()
+    }))
 }
 
 impl Document for Dry {
@@ -163,8 +574,23 @@ impl Document for Dry {
         panic!("`mount` is not supported on `Dry` DOMs")
     }
 
+    fn mount_to(
+        _target: &web_sys::Element,
+        _element: impl Into<GenericElement<Self, Const>>,
+    ) -> MountHandle {
+        panic!("`mount_to` is not supported on `Dry` DOMs")
+    }
+
+    fn mount_append(_id: &str, _element: impl Into<GenericElement<Self, Const>>) -> MountHandle {
+        panic!("`mount_append` is not supported on `Dry` DOMs")
+    }
+
     fn unmount_all() {
-        task::local::with(|local| local.document.mounted_in_dry_head.take());
+        task::local::with(|local| {
+            local.document.mounted_in_dry_head.take();
+            local.document.dry_title.take();
+            local.document.dry_meta.take();
+        });
     }
 
     fn mount_in_head(id: &str, element: impl Into<GenericElement<Self, Mut>>) -> bool {
@@ -175,15 +601,43 @@ impl Document for Dry {
                 return false;
             }
 
-            mounted.insert(id.to_string(), element.into().attribute("id", id).freeze());
+            let nonce = local.document.dry_head_nonce.borrow().clone();
+            mounted.insert(
+                id.to_string(),
+                element
+                    .into()
+                    .attribute("id", id)
+                    .attribute("nonce", nonce)
+                    .freeze(),
+            );
             true
         })
     }
 
+    fn set_nonce(nonce: Option<String>) {
+        task::local::with(|local| local.document.dry_head_nonce.replace(nonce));
+    }
+
     fn head_inner_html() -> String {
         let mut html = String::new();
 
         task::local::with(|local| {
+            if let Some(title) = &*local.document.dry_title.borrow() {
+                html.push_str("<title>");
+                html.push_str(&encode_text_minimal(title));
+                html.push_str("</title>");
+            }
+
+            for (id, meta) in &*local.document.dry_meta.borrow() {
+                html.push_str(&format!(
+                    r#"<meta id="{}" {}="{}" content="{}">"#,
+                    encode_double_quoted_attribute(id),
+                    meta.key_attribute,
+                    encode_double_quoted_attribute(&meta.key),
+                    encode_double_quoted_attribute(&meta.content)
+                ));
+            }
+
             for elem in local.document.mounted_in_dry_head.borrow().values() {
                 html.push_str(&elem.to_string());
             }
@@ -191,16 +645,82 @@ impl Document for Dry {
 
         html
     }
+
+    fn portal(
+        _target: &web_sys::Element,
+        _element: impl Into<GenericElement<Self, Const>>,
+    ) -> PortalHandle {
+        panic!("`portal` is not supported on `Dry` DOMs")
+    }
+
+    fn set_title(title: impl SignalOrValue<Item = impl Into<String> + 'static>) -> TitleHandle {
+        TitleHandle(spawn_setter(title, |title| {
+            task::local::with(|local| local.document.dry_title.replace(Some(title)));
+        }))
+    }
+
+    fn meta(
+        name: &str,
+        content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+    ) -> MetaHandle {
+        dry_meta("name", name, content)
+    }
+
+    fn meta_property(
+        property: &str,
+        content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+    ) -> MetaHandle {
+        dry_meta("property", property, content)
+    }
+}
+
+fn dry_meta(
+    key_attribute: &'static str,
+    key: &str,
+    content: impl SignalOrValue<Item = impl Into<String> + 'static>,
+) -> MetaHandle {
+    let id = meta_id(key_attribute, key);
+
+    task::local::with(|local| {
+        local
+            .document
+            .dry_meta
+            .borrow_mut()
+            .entry(id.clone())
+            .or_insert_with(|| DryMeta {
+                key_attribute,
+                key: key.to_string(),
+                content: String::new(),
+            });
+    });
+
+    MetaHandle(spawn_setter(content, move |content| {
+        task::local::with(|local| {
+            if let Some(meta) = local.document.dry_meta.borrow_mut().get_mut(&id) {
+                meta.content = content;
+            }
+        })
+    }))
+}
+
+fn meta_id(key_attribute: &str, key: &str) -> String {
+    format!("silkenweb-meta-{key_attribute}-{key}")
+}
+
+struct DryMeta {
+    key_attribute: &'static str,
+    key: String,
+    content: String,
 }
 
 /// Manage a mount point
 pub struct MountHandle {
     id: u128,
-    mount_point: web_sys::Element,
+    mount_point: MountPoint,
 }
 
 impl MountHandle {
-    fn new(mount_point: web_sys::Element, element: GenericElement<Wet, Const>) -> Self {
+    fn new(mount_point: MountPoint, element: GenericElement<Wet, Const>) -> Self {
         Self {
             id: insert_element(element),
             mount_point,
@@ -208,21 +728,178 @@ impl MountHandle {
     }
 
     /// Remove the mounted element and restore the mount point.
-    pub fn unmount(self) {
+    ///
+    /// If the element was mounted with [`Document::mount_append`], it's just
+    /// removed from its parent.
+    ///
+    /// The element can be mounted again with [`Unmounted::remount`], or
+    /// dropped by discarding the result. `None` is returned if the element
+    /// has already been removed by [`Document::unmount_all`].
+    pub fn unmount(self) -> Option<Unmounted> {
+        let element = remove_element(self.id)?;
+        let dom_element = element.dom_element();
+
+        match &self.mount_point {
+            MountPoint::Replaced(mount_point) => dom_element
+                .replace_with_with_node_1(mount_point)
+                .unwrap_throw(),
+            MountPoint::Appended(_) => dom_element.remove(),
+        }
+
+        Some(Unmounted {
+            id: self.id,
+            mount_point: self.mount_point,
+            element,
+        })
+    }
+}
+
+/// An element removed with [`MountHandle::unmount`].
+///
+/// This keeps the element alive, so it can be put back in the document
+/// without rebuilding it. The element is dropped with this, unless it's
+/// remounted.
+pub struct Unmounted {
+    id: u128,
+    mount_point: MountPoint,
+    element: GenericElement<Wet, Const>,
+}
+
+impl Unmounted {
+    /// Mount the element again, in the same way it was originally mounted.
+    ///
+    /// The returned [`MountHandle`] manages the same element, so it can be
+    /// cycled in and out of the document as often as required.
+    pub fn remount(self) -> MountHandle {
+        let dom_element = self.element.dom_element();
+
+        match &self.mount_point {
+            MountPoint::Replaced(mount_point) => mount_point
+                .replace_with_with_node_1(&dom_element)
+                .unwrap_throw(),
+            MountPoint::Appended(parent) => parent.append_with_node_1(&dom_element).unwrap_throw(),
+        }
+
+        reinsert_element(self.id, self.element);
+
+        MountHandle {
+            id: self.id,
+            mount_point: self.mount_point,
+        }
+    }
+}
+
+enum MountPoint {
+    /// The element replaced this mount point.
+    Replaced(web_sys::Element),
+    /// The element was appended to this parent.
+    Appended(web_sys::Element),
+}
+
+/// Manage a portal.
+///
+/// The portal's element is removed from the document when this is dropped.
+#[must_use]
+pub struct PortalHandle {
+    id: u128,
+}
+
+impl PortalHandle {
+    fn new(element: GenericElement<Wet, Const>) -> Self {
+        Self {
+            id: insert_element(element),
+        }
+    }
+}
+
+impl Drop for PortalHandle {
+    fn drop(&mut self) {
         if let Some(element) = remove_element(self.id) {
-            element
-                .dom_element()
-                .replace_with_with_node_1(&self.mount_point)
-                .unwrap_throw();
+            element.dom_element().remove()
         }
     }
 }
 
+/// Manage a document title signal.
+///
+/// The title will stop updating when this is dropped.
+#[must_use]
+pub struct TitleHandle(Option<DiscardOnDrop<CancelableFutureHandle>>);
+
+/// Manage a meta element content signal.
+///
+/// The content will stop updating when this is dropped.
+#[must_use]
+pub struct MetaHandle(Option<DiscardOnDrop<CancelableFutureHandle>>);
+
+fn spawn_setter<T: Into<String> + 'static>(
+    value: impl SignalOrValue<Item = T>,
+    set: impl Fn(String) + 'static,
+) -> Option<DiscardOnDrop<CancelableFutureHandle>> {
+    value.select(
+        |set, value| {
+            set(value.into());
+            None
+        },
+        |set, value| {
+            Some(spawn_cancelable_future(value.for_each(move |value| {
+                set(value.into());
+                async {}
+            })))
+        },
+        set,
+    )
+}
+
 thread_local! {
     static MOUNTED_IN_WET_HEAD: RefCell<Vec<GenericElement<Wet, Const>>> = RefCell::new(Vec::new());
+    static HEAD_NONCE: RefCell<Option<String>> = RefCell::new(None);
 }
 
 #[derive(Default)]
 pub(crate) struct TaskLocal {
     mounted_in_dry_head: RefCell<HashMap<String, GenericElement<Dry, Const>>>,
+    dry_title: RefCell<Option<String>>,
+    dry_meta: RefCell<BTreeMap<String, DryMeta>>,
+    dry_head_nonce: RefCell<Option<String>>,
+}
+
+#[cfg_browser(false)]
+mod arch {
+    use crate::task;
+
+    pub async fn view_transition(update: impl FnOnce() + 'static) {
+        update();
+        task::render_now().await;
+    }
+}
+
+#[cfg_browser(true)]
+mod arch {
+    use js_sys::{Function, Promise};
+    use silkenweb_base::document;
+    use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
+    use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+    use crate::task;
+
+    pub async fn view_transition(update: impl FnOnce() + 'static) {
+        let update: Function = Closure::once_into_js(move || {
+            future_to_promise(async move {
+                update();
+                task::render_now().await;
+                Ok(JsValue::UNDEFINED)
+            })
+        })
+        .unchecked_into();
+
+        let finished = document::start_view_transition(&update).unwrap_or_else(|| {
+            update
+                .call0(&JsValue::NULL)
+                .unwrap_throw()
+                .unchecked_into::<Promise>()
+        });
+
+        JsFuture::from(finished).await.unwrap_throw();
+    }
 }
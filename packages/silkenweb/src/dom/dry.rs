@@ -13,7 +13,10 @@ use super::{
     wet::{WetElement, WetNode},
     Dry,
 };
-use crate::{hydration::HydrationStats, node::element::Namespace};
+use crate::{
+    hydration::HydrationStats,
+    node::element::{EventListenerOptions, Namespace, PrettyOptions},
+};
 
 #[derive(Clone)]
 
@@ -79,20 +82,29 @@ impl private::DomElement for DryElement {
     fn on(
         &mut self,
         name: &'static str,
+        options: EventListenerOptions,
         f: impl FnMut(JsValue) + 'static,
         events: &mut EventStore,
     ) {
-        self.0.borrow_mut().on(name, f, events)
+        self.0.borrow_mut().on(name, options, f, events)
     }
 
     fn try_dom_element(&self) -> Option<web_sys::Element> {
         self.0.borrow().try_dom_element()
     }
 
+    fn namespace(&self) -> Namespace {
+        self.0.borrow().namespace()
+    }
+
     fn style_property(&mut self, name: &str, value: &str) {
         self.0.borrow_mut().style_property(name, value)
     }
 
+    fn inner_html(&mut self, html: &str) {
+        self.0.borrow_mut().inner_html(html)
+    }
+
     fn effect(&mut self, f: impl FnOnce(&web_sys::Element) + 'static) {
         self.0.borrow_mut().effect(f)
     }
@@ -108,6 +120,17 @@ impl private::InstantiableDomElement for DryElement {
     }
 }
 
+impl DryElement {
+    pub fn write_pretty(
+        &self,
+        w: &mut impl fmt::Write,
+        options: &PrettyOptions,
+        depth: usize,
+    ) -> fmt::Result {
+        self.0.borrow().write_pretty(w, options, depth)
+    }
+}
+
 impl fmt::Display for DryElement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.borrow().fmt(f)
@@ -148,7 +171,7 @@ pub enum DryNode {
 impl private::InstantiableDomNode for DryNode {
     type DomType = Dry;
 
-    fn into_element(self) -> <Self::DomType as private::Dom>::Element {
+    fn into_element(self, _namespace: Namespace) -> <Self::DomType as private::Dom>::Element {
         match self {
             DryNode::Element(element) => element,
             DryNode::Text(_) => panic!("Text node when expecting element"),
@@ -185,6 +208,28 @@ impl private::InstantiableDomNode for DryNode {
     }
 }
 
+impl DryNode {
+    fn write_pretty(
+        &self,
+        w: &mut impl fmt::Write,
+        options: &PrettyOptions,
+        depth: usize,
+    ) -> fmt::Result {
+        match self {
+            DryNode::Element(element) => element.write_pretty(w, options, depth),
+            DryNode::Text(text) => {
+                let text = text.to_string();
+
+                if text.trim().is_empty() {
+                    return Ok(());
+                }
+
+                writeln!(w, "{:indent$}{text}", "", indent = depth * options.indent)
+            }
+        }
+    }
+}
+
 impl From<DryElement> for DryNode {
     fn from(value: DryElement) -> Self {
         Self::Element(value)
@@ -237,6 +282,7 @@ pub struct SharedDryElement<Node> {
     styles: IndexMap<String, String>,
     children: Vec<Node>,
     shadow_children: Vec<Node>,
+    inner_html: Option<String>,
     hydrate_actions: Vec<LazyElementAction>,
     next_sibling: Option<Node>,
 }
@@ -267,6 +313,7 @@ impl<Node: DryChild> SharedDryElement<Node> {
             styles: IndexMap::new(),
             children: Vec::new(),
             shadow_children: Vec::new(),
+            inner_html: None,
             hydrate_actions: Vec::new(),
             next_sibling: None,
         }
@@ -384,21 +431,36 @@ impl<Node: DryChild> SharedDryElement<Node> {
     pub fn on(
         &mut self,
         name: &'static str,
+        options: EventListenerOptions,
         f: impl FnMut(JsValue) + 'static,
         events: &mut EventStore,
     ) {
         clone!(mut events);
 
-        self.hydrate_actions
-            .push(Box::new(move |element| element.on(name, f, &mut events)))
+        self.hydrate_actions.push(Box::new(move |element| {
+            element.on(name, options, f, &mut events)
+        }))
     }
 
     pub fn try_dom_element(&self) -> Option<web_sys::Element> {
         None
     }
 
+    pub fn namespace(&self) -> Namespace {
+        self.namespace
+    }
+
     pub fn style_property(&mut self, name: &str, value: &str) {
-        self.styles.insert(name.to_owned(), value.to_owned());
+        // Setting a property to an empty string removes it, as in the browser.
+        if value.is_empty() {
+            self.styles.shift_remove(name);
+        } else {
+            self.styles.insert(name.to_owned(), value.to_owned());
+        }
+    }
+
+    pub fn inner_html(&mut self, html: &str) {
+        self.inner_html = Some(html.to_owned());
     }
 
     pub fn effect(&mut self, f: impl FnOnce(&web_sys::Element) + 'static) {
@@ -414,6 +476,7 @@ impl<Node: DryChild> SharedDryElement<Node> {
             styles: self.styles.clone(),
             children: Self::clone_children(&self.children),
             shadow_children: Self::clone_children(&self.shadow_children),
+            inner_html: self.inner_html.clone(),
             hydrate_actions: Vec::new(),
             next_sibling: None,
         }
@@ -480,6 +543,7 @@ impl SharedDryElement<HydroNode> {
         {
             self.hydrate_element(dom_elem, tracker)
         } else {
+            tracker.tag_replaced(dom_elem, new_tag);
             let new_dom_elem = new_namespace.create_element(new_tag);
 
             while let Some(child) = dom_elem.first_child() {
@@ -499,9 +563,17 @@ impl SharedDryElement<HydroNode> {
         tracker: &mut HydrationStats,
     ) -> WetElement {
         self.reconcile_attributes(dom_elem, tracker);
-        let mut elem = WetElement::from_element(dom_elem.clone());
+        let mut elem = WetElement::from_element(dom_elem.clone(), self.namespace);
 
-        Self::hydrate_children(dom_elem, self.children, tracker);
+        if let Some(html) = &self.inner_html {
+            // The browser may normalize the HTML, so this can reset the
+            // content even if it came from the server.
+            if dom_elem.inner_html() != *html {
+                dom_elem.set_inner_html(html);
+            }
+        } else {
+            Self::hydrate_children(dom_elem, self.children, tracker);
+        }
 
         if !self.shadow_children.is_empty() {
             let shadow_root = elem.create_shadow_root();
@@ -537,13 +609,18 @@ impl SharedDryElement<HydroNode> {
             Self::hydrate_with_new(dom_elem, child, tracker);
         }
 
-        Self::remove_children_from(dom_elem, current_child);
+        Self::remove_children_from(dom_elem, current_child, tracker);
     }
 
     /// Remove `child` and all siblings after `child`
-    fn remove_children_from(parent: &web_sys::Node, mut child: Option<web_sys::Node>) {
+    fn remove_children_from(
+        parent: &web_sys::Node,
+        mut child: Option<web_sys::Node>,
+        tracker: &mut HydrationStats,
+    ) {
         while let Some(node) = child {
             let next_child = node.next_sibling();
+            tracker.node_removed(&node);
             parent.remove_child(&node).unwrap_throw();
             child = next_child;
         }
@@ -628,40 +705,125 @@ impl<Node: fmt::Display> SharedDryElement<Node> {
     }
 }
 
-impl<Node: fmt::Display> fmt::Display for SharedDryElement<Node> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "<{}", self.tag)?;
-
-        for (name, value) in &self.attributes {
-            fmt_attr(f, name, value)?;
+impl<Node> SharedDryElement<Node> {
+    fn write_start_tag(&self, w: &mut impl fmt::Write, sort_attributes: bool) -> fmt::Result {
+        write!(w, "<{}", self.tag)?;
+
+        let style = self.style_prop_text();
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .chain(style.as_deref().map(|style| (STYLE_ATTR, style)));
+
+        if sort_attributes {
+            for (name, value) in attributes.sorted_by_key(|(name, _value)| *name) {
+                write_attr(w, name, value)?;
+            }
+        } else {
+            for (name, value) in attributes {
+                write_attr(w, name, value)?;
+            }
         }
 
-        if let Some(style) = self.style_prop_text() {
-            fmt_attr(f, STYLE_ATTR, &style)?;
+        w.write_str(">")
+    }
+
+    fn write_end_tag(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        let has_children = !self.children.is_empty();
+        let requires_closing_tag = !NO_CLOSING_TAG.contains(&self.tag.as_str());
+
+        if requires_closing_tag || has_children {
+            write!(w, "</{}>", self.tag)?;
         }
 
-        f.write_str(">")?;
+        Ok(())
+    }
+}
+
+impl<Node: fmt::Display> fmt::Display for SharedDryElement<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_start_tag(f, false)?;
 
         self.write_shadow_dom(f)?;
 
+        if let Some(html) = &self.inner_html {
+            f.write_str(html)?;
+        }
+
         for child in &self.children {
             child.fmt(f)?;
         }
 
-        let has_children = !self.children.is_empty();
-        let requires_closing_tag = !NO_CLOSING_TAG.contains(&self.tag.as_str());
+        self.write_end_tag(f)
+    }
+}
 
-        if requires_closing_tag || has_children {
-            write!(f, "</{}>", self.tag)?;
+impl SharedDryElement<DryNode> {
+    /// Write each element on its own line, indented by `depth`.
+    ///
+    /// Elements that only contain text or inner HTML, and elements where
+    /// whitespace is significant, such as `<pre>`, are written on a single
+    /// line.
+    fn write_pretty(
+        &self,
+        w: &mut impl fmt::Write,
+        options: &PrettyOptions,
+        depth: usize,
+    ) -> fmt::Result {
+        let indent = depth * options.indent;
+        let shadow_children: &[DryNode] = if cfg!(feature = "declarative-shadow-dom") {
+            &self.shadow_children
+        } else {
+            &[]
+        };
+
+        write!(w, "{:indent$}", "")?;
+        self.write_start_tag(w, options.sort_attributes)?;
+
+        let single_line = shadow_children.is_empty()
+            && (PRESERVE_WHITESPACE.contains(&self.tag.as_str())
+                || self
+                    .children
+                    .iter()
+                    .all(|child| matches!(child, DryNode::Text(_))));
+
+        if single_line {
+            if let Some(html) = &self.inner_html {
+                w.write_str(html)?;
+            }
+
+            for child in &self.children {
+                write!(w, "{child}")?;
+            }
+        } else {
+            writeln!(w)?;
+
+            if !shadow_children.is_empty() {
+                let shadow_indent = indent + options.indent;
+                writeln!(w, r#"{:shadow_indent$}<template shadowroot="open">"#, "")?;
+
+                for child in shadow_children {
+                    child.write_pretty(w, options, depth + 2)?;
+                }
+
+                writeln!(w, "{:shadow_indent$}</template>", "")?;
+            }
+
+            for child in &self.children {
+                child.write_pretty(w, options, depth + 1)?;
+            }
+
+            write!(w, "{:indent$}", "")?;
         }
 
-        Ok(())
+        self.write_end_tag(w)?;
+        writeln!(w)
     }
 }
 
-fn fmt_attr(f: &mut fmt::Formatter, name: &str, value: &str) -> Result<(), fmt::Error> {
-    write!(f, " {}=\"{}\"", name, encode_double_quoted_attribute(value))?;
-    Ok(())
+fn write_attr(w: &mut impl fmt::Write, name: &str, value: &str) -> fmt::Result {
+    write!(w, " {}=\"{}\"", name, encode_double_quoted_attribute(value))
 }
 
 impl<Node: Into<WetNode>> From<SharedDryElement<Node>> for WetElement {
@@ -680,6 +842,10 @@ impl<Node: Into<WetNode>> From<SharedDryElement<Node>> for WetElement {
             wet.append_child(&child.into());
         }
 
+        if let Some(html) = &dry.inner_html {
+            wet.inner_html(html);
+        }
+
         if !dry.shadow_children.is_empty() {
             wet.attach_shadow_children(dry.shadow_children.into_iter().map(|child| child.into()));
         }
@@ -748,13 +914,20 @@ const NO_CLOSING_TAG: &[&str] = &[
     "source", "track", "wbr",
 ];
 
+const PRESERVE_WHITESPACE: &[&str] = &["pre", "textarea"];
+
 const STYLE_ATTR: &str = "style";
 
 #[cfg(test)]
 mod tests {
     use silkenweb_macros::cfg_browser;
 
-    use crate::{dom::Dry, elements::html::*, prelude::*};
+    use crate::{
+        dom::{Dry, Hydro},
+        elements::html::*,
+        node::element::{Const, GenericElement, PrettyOptions},
+        prelude::*,
+    };
     #[cfg_browser(false)]
     use crate::{task::render_now, task::server};
 
@@ -776,6 +949,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pretty_string() {
+        let item = li()
+            .child(img().src("one.png").alt("One"))
+            .child(input().disabled(false));
+        let app: GenericElement<Dry, Const> = div()
+            .id("app")
+            .class("list")
+            .child(ul().child(li().text("One")).child(item))
+            .into();
+
+        assert_eq!(
+            app.to_pretty_string_with(&PrettyOptions {
+                indent: 4,
+                sort_attributes: true,
+            }),
+            r#"<div class="list" id="app">
+    <ul>
+        <li>One</li>
+        <li>
+            <img alt="One" src="one.png">
+            <input>
+        </li>
+    </ul>
+</div>
+"#
+        );
+    }
+
+    const SCRIPT: &str = "<script>alert(1)</script>";
+
+    #[test]
+    fn escape_text() {
+        let app: Div<Dry> = div().text(SCRIPT);
+
+        assert_eq!(
+            app.freeze().to_string(),
+            "<div>&lt;script>alert(1)&lt;/script></div>"
+        );
+    }
+
+    #[test]
+    fn escape_attribute() {
+        let app: Div<Dry> = div().title(SCRIPT).id(r#"a" onclick="alert(1)"#);
+
+        assert_eq!(
+            app.freeze().to_string(),
+            r#"<div title="&lt;script&gt;alert(1)&lt;/script&gt;" id="a&quot; onclick=&quot;alert(1)"></div>"#
+        );
+    }
+
+    #[test]
+    fn escape_hydro_text() {
+        let app: Div<Hydro> = div().child(p().text(SCRIPT));
+
+        assert_eq!(
+            app.freeze().to_string(),
+            "<div><p>&lt;script>alert(1)&lt;/script></p></div>"
+        );
+    }
+
     fn shadow_host() -> Div<Dry> {
         div()
             .attach_shadow_children([slot()])
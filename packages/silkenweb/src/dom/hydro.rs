@@ -4,6 +4,7 @@ use std::{
     rc::Rc,
 };
 
+use html_escape::encode_text_minimal;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
 use super::{
@@ -12,7 +13,10 @@ use super::{
     wet::{WetElement, WetNode, WetText},
     Hydro,
 };
-use crate::{hydration::HydrationStats, node::element::Namespace};
+use crate::{
+    hydration::HydrationStats,
+    node::element::{EventListenerOptions, Namespace},
+};
 
 #[derive(Clone)]
 pub struct HydroElement(Rc<RefCell<SharedHydroElement>>);
@@ -197,12 +201,13 @@ impl DomElement for HydroElement {
     fn on(
         &mut self,
         name: &'static str,
+        options: EventListenerOptions,
         f: impl FnMut(JsValue) + 'static,
         events: &mut EventStore,
     ) {
         match &mut *self.borrow_mut() {
-            SharedHydroElement::Dry(dry) => dry.on(name, f, events),
-            SharedHydroElement::Wet(wet) => wet.on(name, f, events),
+            SharedHydroElement::Dry(dry) => dry.on(name, options, f, events),
+            SharedHydroElement::Wet(wet) => wet.on(name, options, f, events),
             SharedHydroElement::Unreachable => unreachable!(),
         }
     }
@@ -215,6 +220,14 @@ impl DomElement for HydroElement {
         }
     }
 
+    fn namespace(&self) -> Namespace {
+        match &*self.borrow() {
+            SharedHydroElement::Dry(dry) => dry.namespace(),
+            SharedHydroElement::Wet(wet) => wet.namespace(),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
     fn style_property(&mut self, name: &str, value: &str) {
         match &mut *self.borrow_mut() {
             SharedHydroElement::Dry(dry) => dry.style_property(name, value),
@@ -223,6 +236,14 @@ impl DomElement for HydroElement {
         }
     }
 
+    fn inner_html(&mut self, html: &str) {
+        match &mut *self.borrow_mut() {
+            SharedHydroElement::Dry(dry) => dry.inner_html(html),
+            SharedHydroElement::Wet(wet) => wet.inner_html(html),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
     fn effect(&mut self, f: impl FnOnce(&web_sys::Element) + 'static) {
         match &mut *self.borrow_mut() {
             SharedHydroElement::Dry(dry) => dry.effect(f),
@@ -334,7 +355,7 @@ impl HydroText {
 
             let dom_text = new_text.dom_text();
             parent.insert_before(dom_text, Some(child)).unwrap_throw();
-            tracker.node_added(dom_text);
+            tracker.text_added(dom_text, child);
 
             new_text
         }
@@ -381,8 +402,8 @@ impl DomText for HydroText {
 impl fmt::Display for HydroText {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &*self.borrow() {
-            SharedHydroText::Dry(dry) => f.write_str(dry.text()),
-            SharedHydroText::Wet(wet) => f.write_str(&wet.text()),
+            SharedHydroText::Dry(dry) => dry.fmt(f),
+            SharedHydroText::Wet(wet) => encode_text_minimal(&wet.text()).fmt(f),
             SharedHydroText::Unreachable => unreachable!(),
         }
     }
@@ -413,6 +434,17 @@ impl HydroNode {
         self.clone().into()
     }
 
+    pub fn try_into_element(self) -> Result<HydroElement, Self> {
+        match self {
+            Self::Element(element) => Ok(element),
+            Self::Text(_) => Err(self),
+            Self::Wet(node) => node
+                .try_into_element()
+                .map(|element| HydroElement::from_shared(SharedHydroElement::Wet(element)))
+                .map_err(Self::Wet),
+        }
+    }
+
     pub fn hydrate_child(
         self,
         parent: &web_sys::Node,
@@ -470,12 +502,12 @@ impl fmt::Display for HydroNode {
 impl InstantiableDomNode for HydroNode {
     type DomType = Hydro;
 
-    fn into_element(self) -> HydroElement {
+    fn into_element(self, namespace: Namespace) -> HydroElement {
         match self {
             Self::Element(element) => element,
             Self::Text(_) => panic!("Type is `Text`, not `Element`"),
             Self::Wet(node) => {
-                HydroElement::from_shared(SharedHydroElement::Wet(node.into_element()))
+                HydroElement::from_shared(SharedHydroElement::Wet(node.into_element(namespace)))
             }
         }
     }
@@ -2,12 +2,17 @@ use std::fmt::Display;
 
 use wasm_bindgen::JsValue;
 
-use crate::{attribute::Attribute, node::element::Namespace};
+use crate::{
+    attribute::Attribute,
+    node::element::{EventListenerOptions, Namespace},
+};
 
 pub trait Dom: 'static {
     type Element: DomElement<Node = Self::Node>;
     type Text: DomText + Into<Self::Node>;
     type Node: Clone + Display + 'static;
+
+    fn try_into_element(node: Self::Node) -> Result<Self::Element, Self::Node>;
 }
 
 pub trait InstantiableDom:
@@ -45,7 +50,13 @@ pub trait DomElement: Display + Into<Self::Node> + Clone + 'static {
     where
         A: Attribute;
 
-    fn on(&mut self, name: &'static str, f: impl FnMut(JsValue) + 'static, events: &mut EventStore);
+    fn on(
+        &mut self,
+        name: &'static str,
+        options: EventListenerOptions,
+        f: impl FnMut(JsValue) + 'static,
+        events: &mut EventStore,
+    );
 
     fn dom_element(&self) -> web_sys::Element {
         self.try_dom_element().unwrap()
@@ -53,8 +64,12 @@ pub trait DomElement: Display + Into<Self::Node> + Clone + 'static {
 
     fn try_dom_element(&self) -> Option<web_sys::Element>;
 
+    fn namespace(&self) -> Namespace;
+
     fn style_property(&mut self, name: &str, value: &str);
 
+    fn inner_html(&mut self, html: &str);
+
     fn effect(&mut self, f: impl FnOnce(&web_sys::Element) + 'static);
 }
 
@@ -73,7 +88,8 @@ pub trait InstantiableDomElement: DomElement {
 pub trait InstantiableDomNode: Display + Clone {
     type DomType: Dom;
 
-    fn into_element(self) -> <Self::DomType as Dom>::Element;
+    /// Convert a node that's known to be an element, created in `namespace`.
+    fn into_element(self, namespace: Namespace) -> <Self::DomType as Dom>::Element;
 
     fn first_child(&self) -> Self;
 
@@ -84,6 +100,8 @@ pub trait InstantiableDomNode: Display + Clone {
 mod event {
     use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
+    use crate::node::element::EventListenerOptions;
+
     #[derive(Default, Clone)]
     pub struct EventStore {}
 
@@ -92,17 +110,21 @@ mod event {
             &mut self,
             element: &web_sys::Element,
             name: &'static str,
+            options: EventListenerOptions,
             f: impl FnMut(JsValue) + 'static,
         ) {
             element
-                .add_event_listener_with_callback(
+                .add_event_listener_with_callback_and_add_event_listener_options(
                     name,
                     Closure::new(f).into_js_value().unchecked_ref(),
+                    &options.into(),
                 )
                 .unwrap_throw();
         }
 
         pub fn combine(&mut self, _other: Self) {}
+
+        pub fn shrink_to_fit(&mut self) {}
     }
 }
 
@@ -112,6 +134,8 @@ mod event {
 
     use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
+    use crate::node::element::EventListenerOptions;
+
     #[derive(Default, Clone)]
     pub struct EventStore(Rc<RefCell<(Vec<EventCallback>, Vec<Self>)>>);
 
@@ -123,16 +147,22 @@ mod event {
             &mut self,
             element: &web_sys::Element,
             name: &'static str,
+            options: EventListenerOptions,
             f: impl FnMut(JsValue) + 'static,
         ) {
             let callback = Closure::new(f);
             element
-                .add_event_listener_with_callback(name, callback.as_ref().unchecked_ref())
+                .add_event_listener_with_callback_and_add_event_listener_options(
+                    name,
+                    callback.as_ref().unchecked_ref(),
+                    &options.into(),
+                )
                 .unwrap_throw();
 
             self.0.borrow_mut().0.push(EventCallback {
                 element: element.clone(),
                 name,
+                capture: options.capture,
                 callback,
             });
         }
@@ -142,20 +172,34 @@ mod event {
             // to keep track of it's `Rc`.
             self.0.borrow_mut().1.push(other);
         }
+
+        pub fn shrink_to_fit(&mut self) {
+            let (callbacks, children) = &mut *self.0.borrow_mut();
+            callbacks.shrink_to_fit();
+            children.shrink_to_fit();
+
+            for child in children {
+                child.shrink_to_fit();
+            }
+        }
     }
 
     pub struct EventCallback {
         element: web_sys::Element,
         name: &'static str,
+        capture: bool,
         callback: Closure<dyn FnMut(JsValue)>,
     }
 
     impl Drop for EventCallback {
         fn drop(&mut self) {
+            // Listeners are identified by their `capture` flag, as well as the
+            // callback.
             self.element
-                .remove_event_listener_with_callback(
+                .remove_event_listener_with_callback_and_bool(
                     self.name,
                     self.callback.as_ref().as_ref().unchecked_ref(),
+                    self.capture,
                 )
                 .unwrap_throw();
         }
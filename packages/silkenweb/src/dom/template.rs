@@ -8,7 +8,7 @@ use crate::{
         private::{DomElement, DomText, InstantiableDomElement, InstantiableDomNode},
         InstantiableDom,
     },
-    node::element::{GenericElement, Namespace},
+    node::element::{EventListenerOptions, GenericElement, Namespace},
 };
 
 pub struct TemplateElement<Param, D: InstantiableDom> {
@@ -99,20 +99,29 @@ where
     fn on(
         &mut self,
         name: &'static str,
+        options: EventListenerOptions,
         f: impl FnMut(JsValue) + 'static,
         events: &mut EventStore,
     ) {
-        self.element.on(name, f, events)
+        self.element.on(name, options, f, events)
     }
 
     fn try_dom_element(&self) -> Option<web_sys::Element> {
         self.element.try_dom_element()
     }
 
+    fn namespace(&self) -> Namespace {
+        self.element.namespace()
+    }
+
     fn style_property(&mut self, name: &str, value: &str) {
         self.element.style_property(name, value)
     }
 
+    fn inner_html(&mut self, html: &str) {
+        self.element.inner_html(html)
+    }
+
     fn effect(&mut self, f: impl FnOnce(&web_sys::Element) + 'static) {
         self.element.effect(f)
     }
@@ -139,9 +148,28 @@ impl<Param, D: InstantiableDom> Clone for TemplateElement<Param, D> {
 
 pub struct TemplateNode<Param, D: InstantiableDom> {
     node: D::Node,
+    namespace: Option<Namespace>,
     initialization_fns: InitializationFns<Param, D>,
 }
 
+impl<Param, D: InstantiableDom> TemplateNode<Param, D> {
+    pub fn try_into_element(self) -> Result<TemplateElement<Param, D>, Self> {
+        let initialization_fns = self.initialization_fns;
+
+        match D::try_into_element(self.node) {
+            Ok(element) => Ok(TemplateElement {
+                element,
+                initialization_fns,
+            }),
+            Err(node) => Err(Self {
+                node,
+                namespace: self.namespace,
+                initialization_fns,
+            }),
+        }
+    }
+}
+
 impl<Param, D: InstantiableDom> fmt::Display for TemplateNode<Param, D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.node.fmt(f)
@@ -152,6 +180,7 @@ impl<Param, D: InstantiableDom> Clone for TemplateNode<Param, D> {
     fn clone(&self) -> Self {
         Self {
             node: self.node.clone(),
+            namespace: self.namespace,
             initialization_fns: self.initialization_fns.clone(),
         }
     }
@@ -160,6 +189,7 @@ impl<Param, D: InstantiableDom> Clone for TemplateNode<Param, D> {
 impl<Param, D: InstantiableDom> From<TemplateElement<Param, D>> for TemplateNode<Param, D> {
     fn from(elem: TemplateElement<Param, D>) -> Self {
         Self {
+            namespace: Some(elem.element.namespace()),
             node: elem.element.into(),
             initialization_fns: elem.initialization_fns,
         }
@@ -192,6 +222,7 @@ where
     fn from(elem: TemplateText<D>) -> Self {
         Self {
             node: elem.0.into(),
+            namespace: None,
             initialization_fns: InitializationFns::new(),
         }
     }
@@ -321,9 +352,12 @@ where
                     current_index += 1;
                 }
 
+                let namespace = child_template
+                    .namespace
+                    .expect("Only elements have initialization functions");
                 let child_elem = child_template
                     .initialization_fns
-                    .initialize(current_child.clone().into_element(), param);
+                    .initialize(current_child.clone().into_element(namespace), param);
                 element.store_child(child_elem);
             }
         }
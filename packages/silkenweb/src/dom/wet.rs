@@ -1,5 +1,6 @@
 use std::fmt;
 
+use html_escape::encode_text_minimal;
 use silkenweb_base::document;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 use web_sys::{ShadowRootInit, ShadowRootMode};
@@ -8,16 +9,20 @@ use super::{
     private::{DomElement, DomText, EventStore, InstantiableDomElement, InstantiableDomNode},
     Wet,
 };
-use crate::{node::element::Namespace, task::on_animation_frame};
+use crate::{
+    node::element::{EventListenerOptions, Namespace},
+    task::on_animation_frame,
+};
 
 #[derive(Clone)]
 pub struct WetElement {
     element: web_sys::Element,
+    namespace: Namespace,
 }
 
 impl WetElement {
-    pub fn from_element(element: web_sys::Element) -> Self {
-        Self { element }
+    pub fn from_element(element: web_sys::Element, namespace: Namespace) -> Self {
+        Self { element, namespace }
     }
 
     pub fn create_shadow_root(&self) -> web_sys::ShadowRoot {
@@ -41,6 +46,7 @@ impl DomElement for WetElement {
     fn new(ns: Namespace, tag: &str) -> Self {
         Self {
             element: ns.create_element(tag),
+            namespace: ns,
         }
     }
 
@@ -96,16 +102,21 @@ impl DomElement for WetElement {
     fn on(
         &mut self,
         name: &'static str,
+        options: EventListenerOptions,
         f: impl FnMut(JsValue) + 'static,
         events: &mut EventStore,
     ) {
-        events.add_listener(&self.element, name, f);
+        events.add_listener(&self.element, name, options, f);
     }
 
     fn try_dom_element(&self) -> Option<web_sys::Element> {
         Some(self.element.clone())
     }
 
+    fn namespace(&self) -> Namespace {
+        self.namespace
+    }
+
     fn style_property(&mut self, name: &str, value: &str) {
         let style_props = if let Some(elem) = self.element.dyn_ref::<web_sys::HtmlElement>() {
             elem.style()
@@ -118,6 +129,10 @@ impl DomElement for WetElement {
         style_props.set_property(name, value).unwrap_throw();
     }
 
+    fn inner_html(&mut self, html: &str) {
+        self.element.set_inner_html(html)
+    }
+
     fn effect(&mut self, f: impl FnOnce(&web_sys::Element) + 'static) {
         let element = self.element.clone();
         on_animation_frame(move || f(&element));
@@ -140,6 +155,7 @@ impl InstantiableDomElement for WetElement {
                 .clone_node_with_deep(true)
                 .unwrap()
                 .unchecked_into(),
+            namespace: self.namespace,
         }
     }
 }
@@ -175,25 +191,68 @@ impl DomText for WetText {
     }
 }
 
+/// A DOM node, with the namespace it was created in if it's an element.
+///
+/// Nodes found by walking the DOM, with `first_child` or `next_sibling`, don't
+/// know their namespace.
 #[derive(Clone)]
-pub struct WetNode(web_sys::Node);
+pub struct WetNode {
+    node: web_sys::Node,
+    namespace: Option<Namespace>,
+}
 
 impl WetNode {
     pub fn dom_node(&self) -> &web_sys::Node {
-        &self.0
+        &self.node
+    }
+
+    pub fn try_into_element(self) -> Result<WetElement, Self> {
+        let namespace = self.namespace;
+
+        match self.node.dyn_into::<web_sys::Element>() {
+            Ok(element) => {
+                let namespace = namespace.unwrap_or_else(|| known_namespace(&element));
+                Ok(WetElement::from_element(element, namespace))
+            }
+            Err(node) => Err(Self { node, namespace }),
+        }
     }
 
     pub fn clone_node(&self) -> Self {
-        Self(self.0.clone_node_with_deep(true).unwrap_throw())
+        Self {
+            node: self.node.clone_node_with_deep(true).unwrap_throw(),
+            namespace: self.namespace,
+        }
+    }
+
+    fn from_dom(node: web_sys::Node) -> Self {
+        Self {
+            node,
+            namespace: None,
+        }
     }
 }
 
+/// The namespace of an element that we didn't create.
+///
+/// Only the namespaces that have a [`Namespace`] variant are recognized, as
+/// [`Namespace::Other`] needs a static string. Anything else is treated as
+/// HTML.
+fn known_namespace(element: &web_sys::Element) -> Namespace {
+    let uri = element.namespace_uri();
+
+    [Namespace::Svg, Namespace::MathML]
+        .into_iter()
+        .find(|ns| Some(ns.as_str()) == uri.as_deref())
+        .unwrap_or(Namespace::Html)
+}
+
 impl fmt::Display for WetNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(elem) = self.0.dyn_ref::<web_sys::Element>() {
+        if let Some(elem) = self.node.dyn_ref::<web_sys::Element>() {
             f.write_str(&elem.outer_html())
         } else {
-            f.write_str(&self.0.text_content().expect("No text content found"))
+            encode_text_minimal(&self.node.text_content().expect("No text content found")).fmt(f)
         }
     }
 }
@@ -201,29 +260,30 @@ impl fmt::Display for WetNode {
 impl InstantiableDomNode for WetNode {
     type DomType = Wet;
 
-    fn into_element(self) -> WetElement {
-        WetElement {
-            element: self.0.unchecked_into(),
-        }
+    fn into_element(self, namespace: Namespace) -> WetElement {
+        WetElement::from_element(self.node.unchecked_into(), namespace)
     }
 
     fn first_child(&self) -> Self {
-        Self(self.0.first_child().unwrap_throw())
+        Self::from_dom(self.node.first_child().unwrap_throw())
     }
 
     fn next_sibling(&self) -> Self {
-        Self(self.0.next_sibling().unwrap_throw())
+        Self::from_dom(self.node.next_sibling().unwrap_throw())
     }
 }
 
 impl From<WetElement> for WetNode {
     fn from(element: WetElement) -> Self {
-        Self(element.element.into())
+        Self {
+            node: element.element.into(),
+            namespace: Some(element.namespace),
+        }
     }
 }
 
 impl From<WetText> for WetNode {
     fn from(text: WetText) -> Self {
-        Self(text.0.into())
+        Self::from_dom(text.0.into())
     }
 }
@@ -0,0 +1,138 @@
+//! Native HTML drag and drop.
+//!
+//! Use the `on_dragstart`, `on_dragover` and `on_drop` events from
+//! [`HtmlElementEvents`], and get the data being dragged with
+//! [`DataTransfer::from_event`]. An element will only accept drops if it
+//! cancels `dragover` events, so [`HtmlElementEvents::on_file_drop`] does this
+//! for you.
+//!
+//! [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/HTML_Drag_and_Drop_API)
+//!
+//! [`HtmlElementEvents`]: crate::elements::HtmlElementEvents
+//! [`HtmlElementEvents::on_file_drop`]: crate::elements::HtmlElementEvents::on_file_drop
+use wasm_bindgen::JsValue;
+
+use crate::file::file_list_to_vec;
+
+/// The data being dragged.
+///
+/// [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/DataTransfer)
+pub struct DataTransfer(web_sys::DataTransfer);
+
+impl DataTransfer {
+    /// Get the data for a drag event.
+    ///
+    /// This is only `None` if the event was created without a data transfer,
+    /// for example, by a script.
+    pub fn from_event(event: &web_sys::DragEvent) -> Option<Self> {
+        event.data_transfer().map(Self)
+    }
+
+    /// Get the data for `format`, which is usually a MIME type.
+    ///
+    /// `None` is returned if there's no data for `format`, or the data isn't
+    /// available yet. Data is only available from `drop` events.
+    pub fn data(&self, format: &str) -> Option<String> {
+        self.0.get_data(format).ok().filter(|data| !data.is_empty())
+    }
+
+    /// Set the data for `format`, which is usually a MIME type.
+    ///
+    /// # Errors
+    ///
+    /// Data can only be set from a `dragstart` event, otherwise an error is
+    /// returned.
+    pub fn set_data(&self, format: &str, data: &str) -> Result<(), JsValue> {
+        self.0.set_data(format, data)
+    }
+
+    /// Get the `text/plain` data.
+    pub fn text(&self) -> Option<String> {
+        self.data("text/plain")
+    }
+
+    /// Set the `text/plain` data.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::set_data`].
+    pub fn set_text(&self, text: &str) -> Result<(), JsValue> {
+        self.set_data("text/plain", text)
+    }
+
+    /// The formats that have data set.
+    pub fn types(&self) -> Vec<String> {
+        self.0
+            .types()
+            .iter()
+            .filter_map(|format| format.as_string())
+            .collect()
+    }
+
+    /// The files being dragged.
+    ///
+    /// Files are only available from `drop` events.
+    pub fn files(&self) -> Vec<web_sys::File> {
+        file_list_to_vec(self.0.files())
+    }
+
+    /// The effect shown to the user when dragging over an element.
+    pub fn drop_effect(&self) -> DropEffect {
+        match self.0.drop_effect().as_str() {
+            "copy" => DropEffect::Copy,
+            "link" => DropEffect::Link,
+            "move" => DropEffect::Move,
+            _ => DropEffect::None,
+        }
+    }
+
+    /// Set the effect shown to the user when dragging over an element.
+    ///
+    /// This should be set from `dragenter` or `dragover` events.
+    pub fn set_drop_effect(&self, effect: DropEffect) {
+        self.0.set_drop_effect(effect.as_str())
+    }
+
+    /// Set which drop effects are allowed.
+    ///
+    /// This should be set from a `dragstart` event. See [MDN][mdn] for the
+    /// allowed values.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/DataTransfer/effectAllowed
+    pub fn set_effect_allowed(&self, effect_allowed: &str) {
+        self.0.set_effect_allowed(effect_allowed)
+    }
+
+    /// Use `image` as the drag feedback image.
+    ///
+    /// `x` and `y` give the position of the pointer within the image.
+    pub fn set_drag_image(&self, image: &web_sys::Element, x: i32, y: i32) {
+        self.0.set_drag_image(image, x, y)
+    }
+}
+
+/// The visual effect of a drop.
+///
+/// [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/DataTransfer/dropEffect)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DropEffect {
+    /// Dropping isn't allowed.
+    None,
+    /// The data will be copied.
+    Copy,
+    /// A link to the data will be created.
+    Link,
+    /// The data will be moved.
+    Move,
+}
+
+impl DropEffect {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Copy => "copy",
+            Self::Link => "link",
+            Self::Move => "move",
+        }
+    }
+}
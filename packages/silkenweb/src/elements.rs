@@ -28,11 +28,17 @@
 //! [`ParentElement`]: crate::node::element::ParentElement
 //! [`ShadowRootParent`]: crate::node::element::ShadowRootParent
 
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
-use wasm_bindgen::JsCast;
+use silkenweb_signals_ext::value::Value;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast};
 
-use crate::node::element::Element;
+use crate::{
+    attribute::{AsAttribute, Attribute},
+    drag_drop::DataTransfer,
+    keyboard::{self, Key, Mods},
+    node::element::Element,
+};
 
 pub mod html;
 pub mod svg;
@@ -58,12 +64,97 @@ impl<T: JsCast> CustomEvent<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::de::DeserializeOwned> CustomEvent<T> {
+    /// The event detail, deserialized into `T`.
+    ///
+    /// See [`event_detail`].
+    pub fn deserialize_detail(&self) -> Result<T, serde_wasm_bindgen::Error> {
+        event_detail(&self.0)
+    }
+}
+
 impl<T> From<web_sys::CustomEvent> for CustomEvent<T> {
     fn from(src: web_sys::CustomEvent) -> Self {
         Self(src, PhantomData)
     }
 }
 
+/// Deserialize the detail of a custom event.
+///
+/// This saves writing `wasm_bindgen` bindings for the detail of each event
+/// when wrapping web components. Details that can't be deserialized, such as
+/// those that contain DOM elements, can still be read with
+/// [`CustomEvent::detail`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use serde::Deserialize;
+/// # use silkenweb::elements::event_detail;
+/// #[derive(Deserialize)]
+/// struct SelectionChange {
+///     values: Vec<String>,
+///     dates: Vec<u64>,
+/// }
+///
+/// fn on_selection_change(event: web_sys::CustomEvent) {
+///     let detail: SelectionChange = event_detail(&event).unwrap();
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub fn event_detail<T: serde::de::DeserializeOwned>(
+    event: &web_sys::CustomEvent,
+) -> Result<T, serde_wasm_bindgen::Error> {
+    serde_wasm_bindgen::from_value(event.detail())
+}
+
+/// Values for the [`popover`] attribute.
+///
+/// See [`HtmlElement::popover`].
+///
+/// [`popover`]: https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/popover
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PopoverMode {
+    /// The popover is closed by clicking outside it, pressing escape, or
+    /// opening another `Auto` popover.
+    Auto,
+    /// The popover is only closed explicitly.
+    Manual,
+}
+
+impl Attribute for PopoverMode {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(Cow::from(match self {
+            Self::Auto => "auto",
+            Self::Manual => "manual",
+        }))
+    }
+}
+
+impl AsAttribute<PopoverMode> for PopoverMode {}
+
+impl Value for PopoverMode {}
+
+#[wasm_bindgen]
+extern "C" {
+    /// A [`ToggleEvent`][mdn], fired when a popover is shown or hidden.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ToggleEvent
+    #[wasm_bindgen(extends = web_sys::Event)]
+    #[derive(Clone, Debug)]
+    pub type ToggleEvent;
+
+    /// The state the element is changing from, either `"open"` or
+    /// `"closed"`.
+    #[wasm_bindgen(method, getter, js_name = oldState)]
+    pub fn old_state(this: &ToggleEvent) -> String;
+
+    /// The state the element is changing to, either `"open"` or `"closed"`.
+    #[wasm_bindgen(method, getter, js_name = newState)]
+    pub fn new_state(this: &ToggleEvent) -> String;
+}
+
 macro_rules! global_attributes {
     ($($t:tt)*) => {
         attributes![
@@ -159,6 +250,13 @@ pub trait HtmlElement: Element {
         /// document. Its purpose is to identify the element when linking (using
         /// a fragment identifier), scripting, or styling (with CSS).
         id: String,
+        /// A Boolean attribute indicating that the browser should ignore the
+        /// element and its descendants. They can't be focused or clicked,
+        /// their text can't be selected or found with find-in-page, and
+        /// they're hidden from assistive technologies. This is useful for
+        /// disabling the rest of the page while a custom modal is open. Modal
+        /// `<dialog>`s do this without needing `inert`.
+        inert: bool,
         /// Provides a hint to browsers as to the type of virtual keyboard
         /// configuration to use when editing this element or its contents. Used
         /// primarily on `<input>` elements, but is usable on any element while
@@ -205,6 +303,12 @@ pub trait HtmlElement: Element {
         /// allows CSS to select and style specific elements in a shadow tree
         /// via the ::part pseudo-element.
         part: String,
+        /// Makes the element a popover, which is hidden until it's shown with
+        /// a `popovertarget` button or [`ElementHandle::show_popover`].
+        /// Popovers are shown in the top layer, above the rest of the page.
+        ///
+        /// [`ElementHandle::show_popover`]: crate::node::element::ElementHandle::show_popover
+        popover: PopoverMode,
         /// Assigns a slot in a shadow DOM shadow tree to an element: An element
         /// with a slot attribute is assigned to the slot created by the
         /// `<slot>` element whose name attribute's value matches that
@@ -253,6 +357,57 @@ pub trait HtmlElement: Element {
         /// - no, which indicates that the element will not be translated.
         translate: String,
     ];
+
+    /// Set the `part` attribute from a list of part names.
+    ///
+    /// Elements in a shadow tree with a part name can be styled from outside
+    /// with the [`::part`][mdn] pseudo-element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{button, Button};
+    /// # use silkenweb::{dom::Dry, prelude::*};
+    /// let b: Button<Dry> = button().parts(["button", "primary"]);
+    /// assert_eq!(b.freeze().to_string(), r#"<button part="button primary"></button>"#);
+    /// ```
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/CSS/::part
+    fn parts<'a>(self, names: impl IntoIterator<Item = &'a str>) -> Self {
+        self.part(names.into_iter().collect::<Vec<_>>().join(" "))
+    }
+
+    /// Set the `exportparts` attribute of a shadow host.
+    ///
+    /// Each item is a pair of the part name in the shadow tree, and the name
+    /// it's exported as. This lets parts of nested shadow trees be styled from
+    /// outside the outermost shadow host.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*};
+    /// let d: Div<Dry> = div().export_parts([("label", "label"), ("button", "menu-button")]);
+    /// assert_eq!(
+    ///     d.freeze().to_string(),
+    ///     r#"<div exportparts="label, button:menu-button"></div>"#
+    /// );
+    /// ```
+    fn export_parts<'a>(self, parts: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let parts: Vec<_> = parts
+            .into_iter()
+            .map(|(name, exported_name)| {
+                if name == exported_name {
+                    name.to_string()
+                } else {
+                    format!("{name}:{exported_name}")
+                }
+            })
+            .collect();
+
+        self.exportparts(parts.join(", "))
+    }
 }
 
 /// Events common to all HTML elements
@@ -270,7 +425,37 @@ pub trait HtmlElementEvents: Element {
         dragstart: web_sys::DragEvent,
         drop: web_sys::DragEvent,
         load: web_sys::Event,
+        beforetoggle: ToggleEvent,
+        toggle: ToggleEvent,
     });
+
+    /// Handle files dropped on the element.
+    ///
+    /// This also cancels `dragover` events, so the element accepts drops, and
+    /// stops the browser opening the dropped files.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let file_names = Mutable::new(Vec::new());
+    ///
+    /// # let d: Div =
+    /// div().text("Drop files here").on_file_drop(move |files| {
+    ///     file_names.set(files.iter().map(|file| file.name()).collect())
+    /// });
+    /// ```
+    fn on_file_drop(self, mut f: impl FnMut(Vec<web_sys::File>) + 'static) -> Self {
+        self.on_dragover(|event, _| event.prevent_default())
+            .on_drop(move |event, _| {
+                event.prevent_default();
+
+                if let Some(data_transfer) = DataTransfer::from_event(&event) {
+                    f(data_transfer.files())
+                }
+            })
+    }
 }
 
 /// Events common to all elements
@@ -331,6 +516,67 @@ pub trait ElementEvents: Element {
         cut: web_sys::Event,
         paste: web_sys::Event,
     });
+
+    /// Handle `keydown` events for `key`.
+    ///
+    /// `f` is called whatever modifiers are held. Use [`Self::on_key_combo`]
+    /// to match modifiers as well.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{input, Input};
+    /// # use silkenweb::{keyboard::Key, prelude::*};
+    /// let submitted = Mutable::new(String::new());
+    ///
+    /// # let i: Input =
+    /// input().on_key(Key::Enter, move |_, input| submitted.set(input.value()));
+    /// ```
+    fn on_key(
+        self,
+        key: Key,
+        mut f: impl FnMut(web_sys::KeyboardEvent, Self::DomElement) + 'static,
+    ) -> Self {
+        self.on_keydown(move |event, elem| {
+            if key.matches(&event) {
+                f(event, elem)
+            }
+        })
+    }
+
+    /// Handle `keydown` events for `key`, with exactly the modifiers in `mods`
+    /// held.
+    ///
+    /// `key` is a [key value], compared ignoring case. See
+    /// [`keyboard::matches_combo`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::{keyboard::Mods, prelude::*};
+    /// # let d: Div =
+    /// div().on_key_combo(Mods::CTRL, "s", |event, _| {
+    ///     event.prevent_default();
+    ///     // Save the document
+    /// });
+    /// ```
+    ///
+    /// [key value]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+    fn on_key_combo(
+        self,
+        mods: Mods,
+        key: &str,
+        mut f: impl FnMut(web_sys::KeyboardEvent, Self::DomElement) + 'static,
+    ) -> Self {
+        let key = key.to_string();
+
+        self.on_keydown(move |event, elem| {
+            if keyboard::matches_combo(&event, mods, &key) {
+                f(event, elem)
+            }
+        })
+    }
 }
 
 macro_rules! aria_attributes {
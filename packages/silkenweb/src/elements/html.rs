@@ -3531,6 +3531,154 @@ html_element!(
     }
 );
 
+impl<D: crate::dom::Dom> Input<D> {
+    /// Handle files chosen with an `<input type="file">`.
+    ///
+    /// `f` is called with the chosen files each time the selection changes.
+    /// Use [`read_file_text`] or [`read_file_bytes`] to read them.
+    ///
+    /// [`read_file_text`]: crate::file::read_file_text
+    /// [`read_file_bytes`]: crate::file::read_file_bytes
+    pub fn on_files(self, mut f: impl FnMut(Vec<web_sys::File>) + 'static) -> Self {
+        use crate::elements::HtmlElementEvents;
+
+        self.on_change(move |_, input| f(crate::file::file_list_to_vec(input.files())))
+    }
+}
+
+/// Add `on_value` and `value_signal_in` to a form element, where `$event` is
+/// fired when the user changes the value.
+///
+/// If `$on_options_change` is given, it's called with the element and a
+/// function to re-apply the bound value each time the options change.
+macro_rules! value_bindings {
+    ($camel_name:ident, $event:ident $(, $on_options_change:ident)?) => {
+        impl<D: crate::dom::Dom> $camel_name<D> {
+            /// Call `f` with the new value each time the user changes it.
+            pub fn on_value(self, mut f: impl FnMut(String) + 'static) -> Self {
+                use crate::elements::HtmlElementEvents;
+
+                self.$event(move |_, elem| f(elem.value()))
+            }
+
+            /// Bind the element's value to `value`.
+            ///
+            /// The binding is two way: changes to `value` are written to the
+            /// element's `value` property, and changes made by the user are
+            /// written back to `value`.
+            ///
+            /// The property is only set in the browser, so server side
+            /// rendering won't include the value.
+            pub fn value_signal_in(self, value: &futures_signals::signal::Mutable<String>) -> Self {
+                use crate::{elements::HtmlElementEvents, node::element::Element};
+
+                let user_value = value.clone();
+
+                let elem = self
+                    .effect_signal(value.signal_cloned(), |elem, value| {
+                        // Setting the value while the user is typing would move the cursor.
+                        if elem.value() != value {
+                            elem.set_value(&value);
+                        }
+                    })
+                    .$event(move |_, elem| user_value.set_neq(elem.value()));
+
+                $(
+                    let value = value.clone();
+                    let elem = $on_options_change(elem, move |elem| {
+                        let value = value.lock_ref();
+
+                        if elem.value() != *value {
+                            elem.set_value(&value);
+                        }
+                    });
+                )?
+
+                elem
+            }
+        }
+    };
+}
+
+value_bindings!(Input, on_input);
+
+impl<D: crate::dom::Dom> Input<D> {
+    /// Call `f` with the new checked state each time the user changes it.
+    ///
+    /// This is for `<input type="checkbox">` and `<input type="radio">`.
+    /// Browsers only fire `change` for the radio button that was checked, not
+    /// the ones that were unchecked, so `f` will only be called with `true`
+    /// for radio buttons.
+    pub fn on_checked(self, mut f: impl FnMut(bool) + 'static) -> Self {
+        use crate::elements::HtmlElementEvents;
+
+        self.on_change(move |_, input| f(input.checked()))
+    }
+
+    /// Bind the element's checked state to `checked`.
+    ///
+    /// The binding is two way, like [`Input::value_signal_in`]. For a group of
+    /// radio buttons, the unchecked buttons aren't written back, so use
+    /// [`Input::on_checked`] to set a single `Mutable` for the whole group
+    /// instead.
+    pub fn checked_signal_in(self, checked: &futures_signals::signal::Mutable<bool>) -> Self {
+        use crate::{elements::HtmlElementEvents, node::element::Element};
+
+        let user_checked = checked.clone();
+
+        self.effect_signal(checked.signal(), |elem, checked| elem.set_checked(checked))
+            .on_change(move |_, input| user_checked.set_neq(input.checked()))
+    }
+
+    /// Bind the element's value to `value`, parsing it as a `T`.
+    ///
+    /// This is intended for `<input type="number">`. Each time the user
+    /// changes the value, the result of parsing it is written to `value`, so
+    /// parse errors can be displayed reactively. An empty input is passed to
+    /// [`FromStr`] like any other, so for `f64` it's an error.
+    ///
+    /// When `value` is set to `Ok(_)`, the element's value is updated, unless
+    /// it already parses to the same thing. This means partially typed values,
+    /// like `1.0` while the user is typing `1.05`, aren't overwritten. Setting
+    /// `value` to an error leaves the element's value as it is.
+    ///
+    /// Browsers always use `.` as the decimal separator for the `value` of
+    /// `<input type="number">`, whatever the locale, so parsing is locale
+    /// independent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use futures_signals::signal::Mutable;
+    /// # use silkenweb::{elements::html::{input, Input}, prelude::*};
+    /// let amount = Mutable::new(Ok(1.0));
+    /// let app: Input = input().r#type("number").bind_parsed::<f64>(&amount);
+    /// ```
+    ///
+    /// [`FromStr`]: std::str::FromStr
+    pub fn bind_parsed<T>(self, value: &futures_signals::signal::Mutable<Result<T, T::Err>>) -> Self
+    where
+        T: std::str::FromStr + std::fmt::Display + Clone + PartialEq + 'static,
+        T::Err: 'static,
+    {
+        use crate::{elements::HtmlElementEvents, node::element::Element};
+
+        let user_value = value.clone();
+
+        self.effect_signal(
+            value.signal_ref(|value| value.as_ref().ok().cloned()),
+            |elem, value| {
+                if let Some(value) = value {
+                    if elem.value().trim().parse::<T>().ok().as_ref() != Some(&value) {
+                        elem.set_value(&value.to_string());
+                    }
+                }
+            },
+        )
+        .on_input(move |_, input| user_value.set(input.value().trim().parse()))
+    }
+}
+
 html_element!(
     /// The [HTML `<label>` element][mdn] represents a caption for an item in a
     /// user interface.
@@ -3786,6 +3934,10 @@ html_element!(
     /// The [HTML `<select>` element][mdn] represents a control that provides a
     /// menu of options.
     ///
+    /// Bind the selected value with [`Select::value_signal_in`], or the
+    /// selected values of a `<select multiple>` with
+    /// [`Select::selected_many`].
+    ///
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTML/Element/select
     select = {
         dom_type: web_sys::HtmlSelectElement;
@@ -3845,6 +3997,101 @@ html_element!(
 
 parent_element!(select);
 
+value_bindings!(Select, on_change, on_options_change);
+
+impl<D: crate::dom::Dom> Select<D> {
+    /// Render an `<option>` for each `(value, label)` in `options`.
+    ///
+    /// Use [`Select::value_signal_in`] to bind the selected value, or
+    /// [`Select::selected_many`] for a `<select multiple>`. The bound value is
+    /// re-applied each time the options change, so an option that's added
+    /// later is selected if it matches.
+    pub fn options_signal(
+        self,
+        options: impl futures_signals::signal_vec::SignalVec<Item = (String, String)> + 'static,
+    ) -> Self {
+        use futures_signals::signal_vec::SignalVecExt;
+
+        use crate::node::element::ParentElement;
+
+        self.children_signal(options.map(|(value, label)| option::<D>().value(value).text(label)))
+    }
+
+    /// Bind the selected values of a `<select multiple>` to `selected`.
+    ///
+    /// The binding is two way, like [`Select::value_signal_in`]. Options with
+    /// a value in `selected` are selected, and all other options are
+    /// deselected.
+    pub fn selected_many(self, selected: &futures_signals::signal::Mutable<Vec<String>>) -> Self {
+        use crate::{elements::HtmlElementEvents, node::element::Element};
+
+        let user_selected = selected.clone();
+        let current = selected.clone();
+
+        let elem = self
+            .effect_signal(selected.signal_cloned(), |elem, selected| {
+                set_selected_options(elem, &selected)
+            })
+            .on_change(move |_, elem| {
+                user_selected.set_neq(
+                    select_options(&elem)
+                        .filter(|option| option.selected())
+                        .map(|option| option.value())
+                        .collect(),
+                )
+            });
+
+        on_options_change(elem, move |elem| {
+            set_selected_options(elem, &current.lock_ref())
+        })
+    }
+}
+
+/// Call `reapply` each time the options of `select` change.
+///
+/// Adding or removing options can change the selection, so the bindings use
+/// this to put the bound value back.
+fn on_options_change<D: crate::dom::Dom>(
+    select: Select<D>,
+    reapply: impl Fn(&web_sys::HtmlSelectElement) + 'static,
+) -> Select<D> {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::node::element::{Element, MutationObserver};
+
+    let observer = Rc::new(RefCell::new(None));
+
+    select
+        .effect({
+            let observer = observer.clone();
+
+            move |elem| {
+                let select = elem.clone();
+
+                observer.replace(Some(MutationObserver::children(elem, move || {
+                    reapply(&select)
+                })));
+            }
+        })
+        .keep_alive(observer)
+}
+
+fn set_selected_options(select: &web_sys::HtmlSelectElement, selected: &[String]) {
+    for option in select_options(select) {
+        option.set_selected(selected.contains(&option.value()));
+    }
+}
+
+fn select_options(
+    select: &web_sys::HtmlSelectElement,
+) -> impl Iterator<Item = web_sys::HtmlOptionElement> + '_ {
+    use wasm_bindgen::JsCast;
+
+    (0..select.length())
+        .filter_map(|index| select.item(index))
+        .filter_map(|elem| elem.dyn_into().ok())
+}
+
 html_element!(
     /// The [HTML `<textarea>` element][mdn] represents a multi-line plain-text
     /// editing control, useful when you want to allow users to enter a
@@ -3972,6 +4219,8 @@ html_element!(
 
 parent_element!(textarea);
 
+value_bindings!(Textarea, on_input);
+
 html_element!(
     /// The (`<slot>`)[mdn] HTML element—part of the Web Components technology
     /// suite—is a placeholder inside a web component that you can fill with
@@ -3990,3 +4239,50 @@ html_element!(
         };
     }
 );
+
+parent_element!(slot);
+
+impl<D: crate::dom::Dom> Slot<D> {
+    /// Handle changes to the elements assigned to this slot.
+    ///
+    /// `f` is called with the assigned elements on each `slotchange` event,
+    /// for example, when light DOM children are added or removed. Text nodes
+    /// aren't included. Use [`Self::on_slotted_nodes`] if you need them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{slot, Slot};
+    /// # use silkenweb::prelude::*;
+    /// let tab_count = Mutable::new(0);
+    ///
+    /// # let s: Slot =
+    /// slot().on_slotted_elements(move |elements| tab_count.set(elements.len()));
+    /// ```
+    pub fn on_slotted_elements(self, mut f: impl FnMut(Vec<web_sys::Element>) + 'static) -> Self {
+        use wasm_bindgen::JsCast;
+
+        self.on_slotchange(move |_, slot| {
+            f(slot
+                .assigned_elements()
+                .iter()
+                .map(|elem| elem.unchecked_into())
+                .collect())
+        })
+    }
+
+    /// Handle changes to the nodes assigned to this slot.
+    ///
+    /// This is like [`Self::on_slotted_elements`], but includes text nodes.
+    pub fn on_slotted_nodes(self, mut f: impl FnMut(Vec<web_sys::Node>) + 'static) -> Self {
+        use wasm_bindgen::JsCast;
+
+        self.on_slotchange(move |_, slot| {
+            f(slot
+                .assigned_nodes()
+                .iter()
+                .map(|node| node.unchecked_into())
+                .collect())
+        })
+    }
+}
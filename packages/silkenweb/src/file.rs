@@ -0,0 +1,126 @@
+//! Reading files chosen by the user.
+//!
+//! Get files from an `<input type="file">` with [`Input::on_files`], or from a
+//! drop with [`HtmlElementEvents::on_file_drop`], then read them with
+//! [`read_file_text`] or [`read_file_bytes`].
+//!
+//! [`Input::on_files`]: crate::elements::html::Input::on_files
+//! [`HtmlElementEvents::on_file_drop`]: crate::elements::HtmlElementEvents::on_file_drop
+use futures::channel::oneshot;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
+
+/// Why a file couldn't be read.
+///
+/// [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/FileReader/error)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileReadError {
+    /// The file doesn't exist any more, for example, it was moved or deleted
+    /// after the user chose it.
+    NotFound,
+    /// The file couldn't be read, for example, because of a permissions
+    /// problem.
+    NotReadable,
+    /// The browser thinks reading the file is unsafe.
+    Security,
+    /// Any other error, with the name of the `DOMException`.
+    Other(String),
+}
+
+impl FileReadError {
+    fn from_dom_exception(error: &web_sys::DomException) -> Self {
+        match error.name().as_str() {
+            "NotFoundError" => Self::NotFound,
+            "NotReadableError" => Self::NotReadable,
+            "SecurityError" => Self::Security,
+            name => Self::Other(name.to_string()),
+        }
+    }
+}
+
+/// Read the contents of a file as UTF-8 text.
+///
+/// Any [`web_sys::File`] can be read, as it derefs to a [`web_sys::Blob`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use html::{input, Input};
+/// # use silkenweb::{file::read_file_text, prelude::*, task::spawn_local};
+/// let contents = Mutable::new(String::new());
+///
+/// # let i: Input =
+/// input().r#type("file").on_files(move |files| {
+///     for file in files {
+///         clone!(contents);
+///
+///         spawn_local(async move {
+///             if let Ok(text) = read_file_text(&file).await {
+///                 contents.set(text)
+///             }
+///         })
+///     }
+/// });
+/// ```
+pub async fn read_file_text(file: &web_sys::Blob) -> Result<String, FileReadError> {
+    let text = read(file, web_sys::FileReader::read_as_text).await?;
+    Ok(text.as_string().unwrap_throw())
+}
+
+/// Read the contents of a file as bytes.
+///
+/// See [`read_file_text`].
+pub async fn read_file_bytes(file: &web_sys::Blob) -> Result<Vec<u8>, FileReadError> {
+    let buffer = read(file, web_sys::FileReader::read_as_array_buffer).await?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+pub(crate) fn file_list_to_vec(files: Option<web_sys::FileList>) -> Vec<web_sys::File> {
+    files.map_or_else(Vec::new, |files| {
+        (0..files.length())
+            .map(|index| files.get(index).unwrap_throw())
+            .collect()
+    })
+}
+
+async fn read(
+    file: &web_sys::Blob,
+    start: impl FnOnce(&web_sys::FileReader, &web_sys::Blob) -> Result<(), JsValue>,
+) -> Result<JsValue, FileReadError> {
+    let reader = web_sys::FileReader::new().unwrap_throw();
+    let (loaded, on_loaded) = oneshot::channel();
+    // `loadend` fires after the read succeeds or fails.
+    let on_loadend = Closure::once(move || {
+        let _ = loaded.send(());
+    });
+    reader.set_onloadend(Some(on_loadend.as_ref().unchecked_ref()));
+    let read = Read {
+        reader,
+        _on_loadend: on_loadend,
+    };
+
+    start(&read.reader, file).unwrap_throw();
+    on_loaded.await.unwrap_throw();
+
+    if let Some(error) = read.reader.error() {
+        return Err(FileReadError::from_dom_exception(&error));
+    }
+
+    Ok(read.reader.result().unwrap_throw())
+}
+
+/// Abort the read if it's dropped before it finishes, so the `loadend`
+/// callback isn't called after it's dropped.
+struct Read {
+    reader: web_sys::FileReader,
+    _on_loadend: Closure<dyn FnMut()>,
+}
+
+impl Drop for Read {
+    fn drop(&mut self) {
+        self.reader.set_onloadend(None);
+
+        if self.reader.ready_state() == web_sys::FileReader::LOADING {
+            self.reader.abort();
+        }
+    }
+}
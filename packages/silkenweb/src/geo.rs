@@ -0,0 +1,205 @@
+//! Geolocation.
+//!
+//! See [`position_signal`].
+use futures_signals::signal::Signal;
+use silkenweb_macros::cfg_browser;
+
+/// A geographic position.
+///
+/// [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/GeolocationCoordinates)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coordinates {
+    /// Latitude in decimal degrees.
+    pub latitude: f64,
+    /// Longitude in decimal degrees.
+    pub longitude: f64,
+    /// The accuracy of `latitude` and `longitude`, in meters.
+    pub accuracy: f64,
+    /// Altitude in meters, relative to sea level.
+    pub altitude: Option<f64>,
+    /// The accuracy of `altitude`, in meters.
+    pub altitude_accuracy: Option<f64>,
+    /// The direction of travel in degrees, clockwise from true north.
+    pub heading: Option<f64>,
+    /// Speed in meters per second.
+    pub speed: Option<f64>,
+    /// When the position was acquired, in milliseconds since the UNIX epoch.
+    pub timestamp: f64,
+}
+
+/// Why a position couldn't be acquired.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GeoError {
+    /// The user didn't give permission to access their location.
+    PermissionDenied,
+    /// The position couldn't be determined.
+    PositionUnavailable,
+    /// [`PositionOptions::timeout_millis`] elapsed before a position was
+    /// acquired.
+    Timeout,
+    /// Geolocation isn't supported by the browser.
+    Unsupported,
+}
+
+/// Options for [`position_signal`].
+///
+/// [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation/watchPosition#options)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PositionOptions {
+    /// Ask for the most accurate position available.
+    ///
+    /// This may be slower or use more power.
+    pub high_accuracy: bool,
+    /// The maximum age of a cached position that can be used.
+    pub maximum_age_millis: Option<u32>,
+    /// How long to wait for each position.
+    pub timeout_millis: Option<u32>,
+}
+
+/// Watch the user's position.
+///
+/// The signal is `None` until the first position or error arrives, then it's
+/// updated each time the position changes. The user will be asked for
+/// permission, and if they refuse, the signal will be
+/// `Some(Err(GeoError::PermissionDenied))`.
+///
+/// The position is no longer watched once the signal is dropped.
+///
+/// On non-WASM targets, the signal is always `None`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use html::{div, Div};
+/// # use silkenweb::{geo::{position_signal, PositionOptions}, prelude::*};
+/// # let d: Div =
+/// div().text(Sig(position_signal(&PositionOptions::default()).map(
+///     |position| match position {
+///         None => "Locating...".to_string(),
+///         Some(Ok(coords)) => format!("{}, {}", coords.latitude, coords.longitude),
+///         Some(Err(e)) => format!("Couldn't find your location: {e:?}"),
+///     },
+/// )));
+/// ```
+pub fn position_signal(
+    options: &PositionOptions,
+) -> impl Signal<Item = Option<Result<Coordinates, GeoError>>> + 'static {
+    arch::position_signal(options)
+}
+
+#[cfg_browser(false)]
+mod arch {
+    use futures_signals::signal::{always, Signal};
+
+    use super::{Coordinates, GeoError, PositionOptions};
+
+    pub fn position_signal(
+        _options: &PositionOptions,
+    ) -> impl Signal<Item = Option<Result<Coordinates, GeoError>>> + 'static {
+        always(None)
+    }
+}
+
+#[cfg_browser(true)]
+mod arch {
+    use futures_signals::signal::{Mutable, Signal, SignalExt};
+    use silkenweb_base::{clone, window};
+    use wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt};
+
+    use super::{Coordinates, GeoError, PositionOptions};
+
+    pub fn position_signal(
+        options: &PositionOptions,
+    ) -> impl Signal<Item = Option<Result<Coordinates, GeoError>>> + 'static {
+        let position = Mutable::new(None);
+        let watch = match window::geolocation() {
+            Ok(geolocation) => Some(Watch::new(geolocation, options, position.clone())),
+            Err(_) => {
+                position.set(Some(Err(GeoError::Unsupported)));
+                None
+            }
+        };
+
+        position.signal_cloned().map(move |position| {
+            // Keep watching as long as the signal is alive.
+            let _watch = &watch;
+            position
+        })
+    }
+
+    struct Watch {
+        geolocation: web_sys::Geolocation,
+        id: i32,
+        _on_position: Closure<dyn FnMut(web_sys::Position)>,
+        _on_error: Closure<dyn FnMut(web_sys::PositionError)>,
+    }
+
+    impl Watch {
+        fn new(
+            geolocation: web_sys::Geolocation,
+            options: &PositionOptions,
+            position: Mutable<Option<Result<Coordinates, GeoError>>>,
+        ) -> Self {
+            let on_position = Closure::<dyn FnMut(web_sys::Position)>::new({
+                clone!(position);
+                move |new_position: web_sys::Position| {
+                    let coords = new_position.coords();
+
+                    position.set(Some(Ok(Coordinates {
+                        latitude: coords.latitude(),
+                        longitude: coords.longitude(),
+                        accuracy: coords.accuracy(),
+                        altitude: coords.altitude(),
+                        altitude_accuracy: coords.altitude_accuracy(),
+                        heading: coords.heading(),
+                        speed: coords.speed(),
+                        timestamp: new_position.timestamp(),
+                    })))
+                }
+            });
+            let on_error = Closure::<dyn FnMut(web_sys::PositionError)>::new(
+                move |error: web_sys::PositionError| {
+                    let error = match error.code() {
+                        web_sys::PositionError::PERMISSION_DENIED => GeoError::PermissionDenied,
+                        web_sys::PositionError::TIMEOUT => GeoError::Timeout,
+                        _ => GeoError::PositionUnavailable,
+                    };
+
+                    position.set(Some(Err(error)))
+                },
+            );
+
+            let mut js_options = web_sys::PositionOptions::new();
+            js_options.enable_high_accuracy(options.high_accuracy);
+
+            if let Some(maximum_age) = options.maximum_age_millis {
+                js_options.maximum_age(maximum_age);
+            }
+
+            if let Some(timeout) = options.timeout_millis {
+                js_options.timeout(timeout);
+            }
+
+            let id = geolocation
+                .watch_position_with_error_callback_and_options(
+                    on_position.as_ref().unchecked_ref(),
+                    Some(on_error.as_ref().unchecked_ref()),
+                    &js_options,
+                )
+                .unwrap_throw();
+
+            Self {
+                geolocation,
+                id,
+                _on_position: on_position,
+                _on_error: on_error,
+            }
+        }
+    }
+
+    impl Drop for Watch {
+        fn drop(&mut self) {
+            self.geolocation.clear_watch(self.id);
+        }
+    }
+}
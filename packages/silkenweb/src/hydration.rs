@@ -5,7 +5,7 @@
 //! initial page that non-wasm clients can view, whilst wasm-enabled clients
 //! still have a fully interactive app. See [`hydrate`] for more details on how
 //! this is done.
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 use wasm_bindgen::JsCast;
 
@@ -13,6 +13,7 @@ use crate::{
     dom::Hydro,
     insert_element, mount_point,
     node::element::{Const, GenericElement},
+    task,
 };
 
 /// Statistics about the hydration process.
@@ -23,6 +24,7 @@ pub struct HydrationStats {
     empty_text_removed: u64,
     attributes_set: u64,
     attributes_removed: u64,
+    mismatches: Vec<HydrationMismatch>,
 }
 
 impl HydrationStats {
@@ -33,6 +35,7 @@ impl HydrationStats {
             && self.nodes_removed == 0
             && self.attributes_set == 0
             && self.attributes_removed == 0
+            && self.mismatches.is_empty()
     }
 
     /// `true` if there were no diffs between the existing HTML and the element.
@@ -67,8 +70,29 @@ impl HydrationStats {
         self.attributes_removed
     }
 
-    pub(super) fn node_added(&mut self, _elem: &web_sys::Node) {
+    /// Each difference between the existing HTML and the element, except
+    /// for whitespace only text nodes.
+    pub fn mismatches(&self) -> &[HydrationMismatch] {
+        &self.mismatches
+    }
+
+    pub(super) fn node_added(&mut self, node: &web_sys::Node) {
         self.nodes_added += 1;
+        self.mismatch(node, MismatchKind::NodeAdded);
+    }
+
+    pub(super) fn text_added(&mut self, new_text: &web_sys::Node, existing: &web_sys::Node) {
+        self.nodes_added += 1;
+
+        let kind = match existing.dyn_ref::<web_sys::Text>() {
+            Some(existing) => MismatchKind::TextDiffers {
+                expected: new_text.text_content().unwrap_or_default(),
+                existing: existing.text_content().unwrap_or_default(),
+            },
+            None => MismatchKind::NodeAdded,
+        };
+
+        self.mismatch(new_text, kind);
     }
 
     pub(super) fn node_removed(&mut self, node: &web_sys::Node) {
@@ -77,17 +101,147 @@ impl HydrationStats {
             .and_then(|t| t.text_content())
         {
             Some(text) if text.trim().is_empty() => self.empty_text_removed += 1,
-            _ => self.nodes_removed += 1,
+            _ => {
+                self.nodes_removed += 1;
+                self.mismatch(node, MismatchKind::NodeRemoved);
+            }
         }
     }
 
-    pub(super) fn attribute_set(&mut self, _elem: &web_sys::Element, _name: &str, _value: &str) {
+    pub(super) fn tag_replaced(&mut self, elem: &web_sys::Element, new_tag: &str) {
+        self.mismatch(
+            elem,
+            MismatchKind::TagDiffers {
+                expected: new_tag.to_string(),
+                existing: elem.tag_name().to_lowercase(),
+            },
+        );
+    }
+
+    pub(super) fn attribute_set(&mut self, elem: &web_sys::Element, name: &str, value: &str) {
         self.attributes_set += 1;
+        self.mismatch(
+            elem,
+            MismatchKind::AttributeSet {
+                name: name.to_string(),
+                value: value.to_string(),
+            },
+        );
     }
 
-    pub(super) fn attribute_removed(&mut self, _elem: &web_sys::Element, _name: &str) {
+    pub(super) fn attribute_removed(&mut self, elem: &web_sys::Element, name: &str) {
         self.attributes_removed += 1;
+        self.mismatch(
+            elem,
+            MismatchKind::AttributeRemoved {
+                name: name.to_string(),
+            },
+        );
     }
+
+    fn mismatch(&mut self, node: &web_sys::Node, kind: MismatchKind) {
+        self.mismatches.push(HydrationMismatch {
+            path: dom_path(node),
+            kind,
+        });
+    }
+}
+
+/// A difference between the existing HTML and the element being hydrated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HydrationMismatch {
+    path: String,
+    kind: MismatchKind,
+}
+
+impl HydrationMismatch {
+    /// A CSS selector style path to the node, for example `div#app > ul >
+    /// li:nth-child(2) > #text`.
+    ///
+    /// The path starts at the closest ancestor with an `id`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// What the difference was.
+    pub fn kind(&self) -> &MismatchKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for HydrationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.path)?;
+
+        match &self.kind {
+            MismatchKind::NodeAdded => write!(f, "node added"),
+            MismatchKind::NodeRemoved => write!(f, "node removed"),
+            MismatchKind::TagDiffers { expected, existing } => {
+                write!(f, "expected tag `{expected}`, found `{existing}`")
+            }
+            MismatchKind::TextDiffers { expected, existing } => {
+                write!(f, "expected text {expected:?}, found {existing:?}")
+            }
+            MismatchKind::AttributeSet { name, value } => {
+                write!(f, "attribute `{name}` set to {value:?}")
+            }
+            MismatchKind::AttributeRemoved { name } => write!(f, "attribute `{name}` removed"),
+        }
+    }
+}
+
+/// The kind of a [`HydrationMismatch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MismatchKind {
+    /// A node was missing from the existing HTML, so it was added.
+    NodeAdded,
+    /// An extra node was in the existing HTML, so it was removed.
+    NodeRemoved,
+    /// The mount point had a different tag, so it was replaced.
+    TagDiffers { expected: String, existing: String },
+    /// A text node had different text, so a new one was added.
+    TextDiffers { expected: String, existing: String },
+    /// An attribute was missing or had a different value.
+    AttributeSet { name: String, value: String },
+    /// An extra attribute was in the existing HTML, so it was removed.
+    AttributeRemoved { name: String },
+}
+
+fn dom_path(node: &web_sys::Node) -> String {
+    let mut segments = Vec::new();
+
+    if node.dyn_ref::<web_sys::Element>().is_none() {
+        segments.push("#text".to_string());
+    }
+
+    let mut elem = node
+        .dyn_ref::<web_sys::Element>()
+        .cloned()
+        .or_else(|| node.parent_element());
+
+    while let Some(current) = elem {
+        let tag = current.tag_name().to_lowercase();
+        let id = current.id();
+
+        if !id.is_empty() {
+            segments.push(format!("{tag}#{id}"));
+            break;
+        }
+
+        let mut index = 1;
+        let mut sibling = current.previous_element_sibling();
+
+        while let Some(previous) = sibling {
+            index += 1;
+            sibling = previous.previous_element_sibling();
+        }
+
+        segments.push(format!("{tag}:nth-child({index})"));
+        elem = current.parent_element();
+    }
+
+    segments.reverse();
+    segments.join(" > ")
 }
 
 impl fmt::Display for HydrationStats {
@@ -97,7 +251,13 @@ impl fmt::Display for HydrationStats {
         writeln!(f, "    nodes removed = {}", self.nodes_removed)?;
         writeln!(f, "    empty text removed = {}", self.empty_text_removed)?;
         writeln!(f, "    attributes set = {}", self.attributes_set)?;
-        writeln!(f, "    attributes removed = {}", self.attributes_removed)
+        writeln!(f, "    attributes removed = {}", self.attributes_removed)?;
+
+        for mismatch in &self.mismatches {
+            writeln!(f, "    {mismatch}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -112,11 +272,12 @@ impl fmt::Display for HydrationStats {
 /// matching node isn't found, a new one will be created. This way, hydration
 /// never fails, but in the worst case will discard the original HTML. You can
 /// track how well the existing HTML matched `elem` with the returned
-/// [`HydrationStats`]. Generally speaking, extra nodes in the existing document
-/// HTML will be removed, and hydration will continue. Extra nodes within `elem`
-/// will cause the existing HTML to be replaced. This allows extra whitespace
-/// nodes to be introduced to prettify the server HTML, without impacting the
-/// hydration process.
+/// [`HydrationStats`], and [`HydrationStats::mismatches`] lists each
+/// difference along with the path to the node. Generally speaking, extra nodes
+/// in the existing document HTML will be removed, and hydration will continue.
+/// Extra nodes within `elem` will cause the existing HTML to be replaced. This
+/// allows extra whitespace nodes to be introduced to prettify the server HTML,
+/// without impacting the hydration process.
 ///
 /// Attributes will be added or removed when necessary to make sure the exisitng
 /// HTML matches `elem`. Attributes beginning with `data-silkenweb` will be left
@@ -174,7 +335,80 @@ impl fmt::Display for HydrationStats {
 pub async fn hydrate(id: &str, element: impl Into<GenericElement<Hydro, Const>>) -> HydrationStats {
     #[cfg(debug_assertions)]
     crate::log_panics();
-    let element = element.into();
+    hydrate_mount_point(id, element.into())
+}
+
+/// Hydrate some "islands" within the existing HTML.
+///
+/// Each island is a pair of a mount point id, and a function to create the
+/// element to hydrate it with. Each island is hydrated as if [`hydrate`] was
+/// called for it, and the HTML outside the islands is left as it is. This is
+/// useful when most of the page is static, and only a few elements need event
+/// handlers.
+///
+/// Any pending updates are rendered after the islands are created, but before
+/// they're hydrated, so signals will be initialized correctly.
+///
+/// The [`HydrationStats`] for each island are returned, keyed by island id.
+///
+/// # Panics
+///
+/// If there's no element for any island id.
+///
+/// # Example
+///
+/// Islands with different element types must be boxed:
+///
+/// ```no_run
+/// # use html::{button, div};
+/// # use silkenweb::{
+/// #     dom::Hydro,
+/// #     hydration::hydrate_islands,
+/// #     node::element::{Const, GenericElement},
+/// #     prelude::*,
+/// #     task::spawn_local,
+/// # };
+/// type Island = Box<dyn FnOnce() -> GenericElement<Hydro, Const>>;
+///
+/// spawn_local(async {
+///     let stats = hydrate_islands([
+///         (
+///             "counter",
+///             Box::new(|| div().id("counter").text("0").into()) as Island,
+///         ),
+///         (
+///             "menu",
+///             Box::new(|| button().id("menu").text("Menu").into()) as Island,
+///         ),
+///     ])
+///     .await;
+/// });
+/// ```
+pub async fn hydrate_islands<'a, F, E>(
+    islands: impl IntoIterator<Item = (&'a str, F)>,
+) -> HashMap<String, HydrationStats>
+where
+    F: FnOnce() -> E,
+    E: Into<GenericElement<Hydro, Const>>,
+{
+    #[cfg(debug_assertions)]
+    crate::log_panics();
+    let islands: Vec<(&str, GenericElement<Hydro, Const>)> = islands
+        .into_iter()
+        .map(|(id, island)| (id, island().into()))
+        .collect();
+
+    // The islands were created inside this `async` fn, so their signals need to
+    // be initialized before hydration. See the warning on `hydrate`.
+    task::render_now().await;
+
+    islands
+        .into_iter()
+        .map(|(id, element)| (id.to_string(), hydrate_mount_point(id, element)))
+        .collect()
+}
+
+fn hydrate_mount_point(id: &str, element: GenericElement<Hydro, Const>) -> HydrationStats {
     let mut stats = HydrationStats::default();
 
     let mount_point = mount_point(id);
@@ -0,0 +1,185 @@
+//! Match keyboard events against keys and modifiers.
+//!
+//! Use [`ElementEvents::on_key`] and [`ElementEvents::on_key_combo`] to handle
+//! `keydown` events for a particular key, rather than comparing
+//! [`KeyboardEvent::key`] by hand.
+//!
+//! [`ElementEvents::on_key`]: crate::elements::ElementEvents::on_key
+//! [`ElementEvents::on_key_combo`]: crate::elements::ElementEvents::on_key_combo
+//! [`KeyboardEvent::key`]: web_sys::KeyboardEvent::key
+use std::ops::BitOr;
+
+use web_sys::KeyboardEvent;
+
+/// A key, from the [key value] of a keyboard event.
+///
+/// Some older browsers use different names for some keys, for example `"Esc"`
+/// and `"Spacebar"`. These are normalized, so they'll still match.
+///
+/// [key value]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Key {
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Space,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// Any key that produces a single character, other than a space.
+    ///
+    /// Letters are compared ignoring case, so `Key::Char('a')` and
+    /// `Key::Char('A')` both match the `a` key, whether or not shift is held.
+    /// Use [`Mods`] to check for shift.
+    Char(char),
+}
+
+impl Key {
+    /// The key for `event`.
+    ///
+    /// This is `None` for keys that aren't covered by [`Key`], for example
+    /// modifier or function keys. Letters are converted to lower case.
+    pub fn from_event(event: &KeyboardEvent) -> Option<Self> {
+        Self::from_key_value(&event.key())
+    }
+
+    /// Does `event` come from this key?
+    pub fn matches(self, event: &KeyboardEvent) -> bool {
+        Self::from_event(event) == Some(self.normalize())
+    }
+
+    /// Convert letters to lower case, so they match whatever the state of
+    /// shift.
+    fn normalize(self) -> Self {
+        match self {
+            Self::Char(c) => {
+                let mut lower = c.to_lowercase();
+
+                match (lower.next(), lower.next()) {
+                    (Some(lower), None) => Self::Char(lower),
+                    _ => self,
+                }
+            }
+            key => key,
+        }
+    }
+
+    fn from_key_value(key: &str) -> Option<Self> {
+        Some(match key {
+            "Enter" => Self::Enter,
+            "Escape" | "Esc" => Self::Escape,
+            "Tab" => Self::Tab,
+            "Backspace" => Self::Backspace,
+            "Delete" | "Del" => Self::Delete,
+            "Insert" => Self::Insert,
+            " " | "Spacebar" => Self::Space,
+            "ArrowUp" | "Up" => Self::ArrowUp,
+            "ArrowDown" | "Down" => Self::ArrowDown,
+            "ArrowLeft" | "Left" => Self::ArrowLeft,
+            "ArrowRight" | "Right" => Self::ArrowRight,
+            "Home" => Self::Home,
+            "End" => Self::End,
+            "PageUp" => Self::PageUp,
+            "PageDown" => Self::PageDown,
+            key => {
+                let mut chars = key.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Self::Char(c).normalize(),
+                    _ => return None,
+                }
+            }
+        })
+    }
+}
+
+/// A set of modifier keys.
+///
+/// Combine modifiers with `|`, for example `Mods::CTRL | Mods::SHIFT`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Mods(u8);
+
+impl Mods {
+    pub const NONE: Self = Self(0);
+    pub const CTRL: Self = Self(1);
+    pub const SHIFT: Self = Self(1 << 1);
+    pub const ALT: Self = Self(1 << 2);
+    pub const META: Self = Self(1 << 3);
+
+    /// The modifiers that were held down for `event`.
+    pub fn from_event(event: &KeyboardEvent) -> Self {
+        [
+            (event.ctrl_key(), Self::CTRL),
+            (event.shift_key(), Self::SHIFT),
+            (event.alt_key(), Self::ALT),
+            (event.meta_key(), Self::META),
+        ]
+        .into_iter()
+        .filter(|(pressed, _)| *pressed)
+        .fold(Self::NONE, |mods, (_, modifier)| mods | modifier)
+    }
+}
+
+impl BitOr for Mods {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Does `event` come from `key`, with exactly the modifiers in `mods` held?
+///
+/// `key` is a [key value], compared ignoring case. Shift changes the key value
+/// of letters, so `"s"` with `Mods::CTRL | Mods::SHIFT` matches Ctrl+Shift+S,
+/// where the key value is `"S"`. With just `Mods::CTRL`, `"s"` only matches
+/// Ctrl+S, as the modifiers must match exactly.
+///
+/// [key value]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+pub fn matches_combo(event: &KeyboardEvent, mods: Mods, key: &str) -> bool {
+    Mods::from_event(event) == mods && event.key().eq_ignore_ascii_case(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Key;
+
+    #[test]
+    fn legacy_key_values() {
+        for (legacy, key) in [
+            ("Esc", Key::Escape),
+            ("Del", Key::Delete),
+            ("Spacebar", Key::Space),
+            ("Up", Key::ArrowUp),
+            ("Down", Key::ArrowDown),
+            ("Left", Key::ArrowLeft),
+            ("Right", Key::ArrowRight),
+        ] {
+            assert_eq!(Key::from_key_value(legacy), Some(key), "{legacy}");
+        }
+    }
+
+    #[test]
+    fn char_key_values() {
+        assert_eq!(Key::from_key_value("a"), Some(Key::Char('a')));
+        assert_eq!(Key::from_key_value("A"), Some(Key::Char('a')));
+        assert_eq!(Key::from_key_value("1"), Some(Key::Char('1')));
+        assert_eq!(Key::from_key_value(" "), Some(Key::Space));
+        assert_eq!(Key::Char('A').normalize(), Key::Char('a'));
+    }
+
+    #[test]
+    fn unknown_key_values() {
+        assert_eq!(Key::from_key_value("Shift"), None);
+        assert_eq!(Key::from_key_value("F1"), None);
+        assert_eq!(Key::from_key_value(""), None);
+    }
+}
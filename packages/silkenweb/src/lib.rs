@@ -218,6 +218,40 @@ use silkenweb_base::document as base_document;
 /// [`DefaultDom::mount_in_head`]: crate::dom::DefaultDom::mount_in_head
 /// [CSS Modules]: https://github.com/css-modules/css-modules
 pub use silkenweb_macros::css;
+/// Define elements from a [Custom Elements Manifest].
+///
+/// The macro takes the path to a `custom-elements.json` file, relative to the
+/// `$CARGO_MANIFEST_DIR` environment variable. An element is defined with
+/// [`custom_html_element!`] and [`parent_element!`] for each custom element
+/// declared in the manifest. The element's name is the snake case tag name, so
+/// `<ui5-button>` becomes `ui5_button`.
+///
+/// Attributes are typed from the manifest:
+///
+/// - `boolean` attributes are `bool`.
+/// - `number` attributes are `f64`.
+/// - A union of string literals, like `"Default" | "Emphasized"`, becomes an
+///   enum named after the element and attribute, such as `Ui5ButtonDesign`.
+/// - Anything else is a `String`.
+///
+/// `undefined` and `null` are ignored in union types. Each declared event gets
+/// an `on_<event>` method that's passed a [`web_sys::CustomEvent`]. Events
+/// whose names can't be derived from a snake case identifier, such as
+/// `selectionChange`, are skipped.
+///
+/// The manifest is included with `include_bytes!`, so changes to it will
+/// trigger a rebuild.
+///
+/// # Example
+///
+/// ```ignore
+/// # use silkenweb::custom_elements_manifest;
+/// custom_elements_manifest!("node_modules/@ui5/webcomponents/dist/custom-elements.json");
+/// ```
+///
+/// [Custom Elements Manifest]: https://github.com/webcomponents/custom-elements-manifest
+pub use silkenweb_macros::custom_elements_manifest;
+
 /// Derive the traits needed for a blanket implmenetation of [`ChildElement`].
 ///
 /// This only works for structs. It will defer to one field for the
@@ -295,13 +329,18 @@ pub mod animation;
 pub mod attribute;
 pub mod document;
 pub mod dom;
+pub mod drag_drop;
 pub mod elements;
+pub mod file;
+pub mod geo;
 pub mod hydration;
+pub mod keyboard;
 pub mod node;
 pub mod router;
 pub mod storage;
 pub mod task;
 pub mod time;
+pub mod web_component;
 pub mod window;
 
 /// Commonly used imports, all in one place.
@@ -350,6 +389,10 @@ fn insert_element(element: GenericElement<Wet, Const>) -> u128 {
     id
 }
 
+fn reinsert_element(id: u128, element: GenericElement<Wet, Const>) {
+    ELEMENTS.with(|elements| elements.borrow_mut().insert(id, element));
+}
+
 fn remove_element(id: u128) -> Option<GenericElement<Wet, Const>> {
     ELEMENTS.with(|elements| elements.borrow_mut().remove(&id))
 }
@@ -372,11 +415,92 @@ pub fn empty_str() -> &'static str {
     EMPTY.with(|empty| *empty)
 }
 
+/// Intern a string that will be used for the life of the program, and record
+/// it so [`intern_dynamic_str`] can skip it.
+///
+/// This is used by [`intern_static_str!`], and shouldn't be called directly.
+#[doc(hidden)]
+#[cfg_browser(true)]
+pub fn intern_static(s: &'static str) -> &'static str {
+    with_static_interned(|interned| interned.insert((s.as_ptr() as usize, s.len())));
+    intern_str(s)
+}
+
+/// Intern a string that isn't known at compile time, such as an attribute
+/// name.
+///
+/// Names from [`intern_static_str!`] are already interned, so they're found
+/// by their address and length, and returned as they are. Other strings are
+/// interned for the life of the page, as wasm-bindgen has no way to evict a
+/// single entry, so only the first `MAX_DYNAMIC_INTERNED` distinct strings
+/// are interned on each thread. Once that limit is reached, new strings are
+/// returned uninterned, and strings that were already interned stay cached.
+#[cfg_browser(true)]
+pub(crate) fn intern_dynamic_str(s: &str) -> &str {
+    use std::collections::HashSet;
+
+    const MAX_DYNAMIC_INTERNED: usize = 1024;
+
+    thread_local! {
+        static DYNAMIC_INTERNED: RefCell<HashSet<String>> = RefCell::default();
+    }
+
+    let is_static =
+        with_static_interned(|interned| interned.contains(&(s.as_ptr() as usize, s.len())));
+
+    if is_static {
+        return s;
+    }
+
+    let should_intern = DYNAMIC_INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+
+        if interned.contains(s) {
+            true
+        } else if interned.len() < MAX_DYNAMIC_INTERNED {
+            interned.insert(s.to_string());
+            true
+        } else {
+            false
+        }
+    });
+
+    if should_intern {
+        intern_str(s)
+    } else {
+        s
+    }
+}
+
+/// The address and length of each string from [`intern_static`].
+#[cfg_browser(true)]
+type StaticInterned = std::collections::HashSet<(usize, usize)>;
+
+#[cfg_browser(true)]
+fn with_static_interned<R>(f: impl FnOnce(&mut StaticInterned) -> R) -> R {
+    thread_local! {
+        static STATIC_INTERNED: RefCell<StaticInterned> = RefCell::default();
+    }
+
+    STATIC_INTERNED.with(|interned| f(&mut interned.borrow_mut()))
+}
+
 #[cfg_browser(false)]
 pub fn intern_str(s: &str) -> &str {
     s
 }
 
+#[doc(hidden)]
+#[cfg_browser(false)]
+pub fn intern_static(s: &'static str) -> &'static str {
+    s
+}
+
+#[cfg_browser(false)]
+pub(crate) fn intern_dynamic_str(s: &str) -> &str {
+    s
+}
+
 #[cfg_browser(false)]
 pub fn empty_str() -> &'static str {
     ""
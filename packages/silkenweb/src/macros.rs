@@ -4,7 +4,24 @@ pub use silkenweb_macros::rust_to_html_ident;
 pub use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 pub use web_sys;
 
-pub use crate::intern_str;
+pub use crate::{intern_static, intern_str};
+
+/// Intern and dedupe the classes for `class_set!`.
+///
+/// This leaks the list, so it should only be called once per call site, per
+/// thread.
+#[doc(hidden)]
+pub fn intern_class_set(classes: &[&'static str]) -> &'static [&'static str] {
+    let mut unique: Vec<&'static str> = Vec::with_capacity(classes.len());
+
+    for &class in classes {
+        if !unique.contains(&class) {
+            unique.push(intern_str(class));
+        }
+    }
+
+    Vec::leak(unique)
+}
 
 /// Define a custom html element.
 ///
@@ -17,6 +34,26 @@ pub use crate::intern_str;
 /// element or attribute name. See `my_explicitly_named_attribute` in the
 /// example.
 ///
+/// Elements are in the [`Html`] namespace by default. To wrap an element from
+/// another XML vocabulary, put `namespace = Namespace::Other("...");` first.
+/// [`Wet`] elements are created in that namespace. HTML parsers only know
+/// about the HTML, SVG and MathML namespaces, so [`Dry`] elements are
+/// serialized without it, and [`Hydro`] elements are recreated in the right
+/// namespace when they're hydrated.
+///
+/// ```no_run
+/// # use silkenweb::{custom_html_element, node::element::Namespace};
+/// custom_html_element!(my_item = {
+///     namespace = Namespace::Other("urn:example:items");
+///     dom_type: web_sys::Element;
+/// });
+/// ```
+///
+/// [`Html`]: crate::node::element::Namespace::Html
+/// [`Wet`]: crate::dom::Wet
+/// [`Dry`]: crate::dom::Dry
+/// [`Hydro`]: crate::dom::Hydro
+///
 /// # Example
 ///
 /// ```no_run
@@ -47,6 +84,23 @@ pub use crate::intern_str;
 /// ```
 #[macro_export]
 macro_rules! custom_html_element {
+    (
+        $(#[$elem_meta:meta])*
+        $name:ident $( ($text_name: literal) )? = {
+            namespace = $namespace:expr;
+            $($tail:tt)*
+        }
+    ) => {
+        $crate::dom_element!(
+            $(#[$elem_meta])*
+            $name $( ($text_name) )? = {
+                common_attributes = [$crate::elements::HtmlElement, $crate::elements::AriaElement];
+                common_events = [$crate::elements::HtmlElementEvents];
+                namespace = $namespace;
+                $($tail)*
+            }
+        );
+    };
     (
         $(#[$elem_meta:meta])*
         $name:ident $( ($text_name: literal) )? = {
@@ -62,7 +116,7 @@ macro_rules! custom_html_element {
                 $($tail)*
             }
         );
-    }
+    };
 }
 
 macro_rules! html_element {
@@ -343,6 +397,14 @@ macro_rules! dom_element {
                 Self(self.0.spawn_future(future))
             }
 
+            fn keep_alive(self, resource: impl 'static) -> Self {
+                Self(self.0.keep_alive(resource))
+            }
+
+            fn shrink_to_fit(self) -> Self {
+                Self(self.0.shrink_to_fit())
+            }
+
             fn on(
                 self,
                 name: &'static str,
@@ -350,6 +412,15 @@ macro_rules! dom_element {
             ) -> Self {
                 Self($crate::node::element::Element::on(self.0, name, f))
             }
+
+            fn on_with_options(
+                self,
+                name: &'static str,
+                options: $crate::node::element::EventListenerOptions,
+                f: impl FnMut($crate::macros::JsValue) + 'static
+            ) -> Self {
+                Self($crate::node::element::Element::on_with_options(self.0, name, options, f))
+            }
         }
 
         impl<Dom: $crate::dom::Dom, Mutability> $crate::value::Value
@@ -477,6 +548,34 @@ macro_rules! parent_element {
             {
                 Self(self.0.children_signal(children))
             }
+
+            fn dangerous_inner_html<'a>(
+                self,
+                html: impl $crate::value::RefSignalOrValue<'a, Item = impl AsRef<str> + 'a>,
+            ) -> Self {
+                Self(self.0.dangerous_inner_html(html))
+            }
+        }
+
+        impl<InitParam, Dom> [< $name:camel >]<$crate::dom::Template<InitParam, Dom>>
+        where
+            Dom: $crate::dom::InstantiableDom,
+            InitParam: 'static
+        {
+            /// Add a child when the template is instantiated.
+            ///
+            /// `f` is called with the parameter passed to `instantiate`, and
+            /// the child is only added if it returns `Some(..)`. The child
+            /// is added after any other children.
+            pub fn child_on_instantiate<N>(
+                self,
+                f: impl 'static + Fn(&InitParam) -> ::std::option::Option<N>,
+            ) -> Self
+            where
+                N: Into<$crate::node::Node<Dom>>
+            {
+                Self(self.0.child_on_instantiate(f))
+            }
         }
     }};
 }
@@ -516,11 +615,26 @@ macro_rules! events {
             $(#[$event_meta])*
             $visiblity fn [<on_ $name >] (
                 self,
+                f: impl FnMut($event_type, $elem_type) + 'static
+            ) -> Self {
+                self.[<on_ $name _with_options>](
+                    ::std::default::Default::default(),
+                    f
+                )
+            }
+
+            $(#[$event_meta])*
+            #[doc = ""]
+            #[doc = "See `Element::on_with_options` for details of `options`."]
+            $visiblity fn [<on_ $name _with_options>] (
+                self,
+                options: $crate::node::element::EventListenerOptions,
                 mut f: impl FnMut($event_type, $elem_type) + 'static
             ) -> Self {
-                $crate::node::element::Element::on(
+                $crate::node::element::Element::on_with_options(
                     self,
                     $crate::text_name_intern!($name),
+                    options,
                     move |js_ev| {
                         use $crate::macros::JsCast;
                         // I *think* we can assume event and event.current_target aren't null
@@ -689,9 +803,102 @@ macro_rules! text_name {
 macro_rules! intern_static_str {
     ($s:expr) => {{
         ::std::thread_local! {
-            static NAME: &'static str = $crate::macros::intern_str($s);
+            static NAME: &'static str = $crate::macros::intern_static($s);
         }
 
         NAME.with(|name| *name)
     }};
 }
+
+/// Classes from the `class` module generated by [`css!`], as an array.
+///
+/// The first argument is the path to the module, and the second is a list of
+/// class names in that module. Only names are accepted, so a typo or a string
+/// literal is a compile error. The result can be passed to
+/// [`Element::classes`].
+///
+/// With `auto_mount`, the class names are functions, so call them with `()`.
+///
+/// # Example
+///
+/// ```
+/// # use html::{div, Div};
+/// # use silkenweb::{css, dom::Dry, prelude::*, typed_classes};
+/// css!(content = ".panel {} .highlighted {}");
+///
+/// let panel: Div<Dry> = div().classes(typed_classes!(class, [PANEL, HIGHLIGHTED]));
+/// assert_eq!(
+///     panel.freeze().to_string(),
+///     r#"<div class="panel highlighted"></div>"#
+/// );
+/// ```
+///
+/// Strings aren't accepted:
+///
+/// ```compile_fail
+/// # use html::{div, Div};
+/// # use silkenweb::{css, dom::Dry, prelude::*, typed_classes};
+/// css!(content = ".panel {}");
+///
+/// let panel: Div<Dry> = div().classes(typed_classes!(class, ["panel"]));
+/// ```
+///
+/// Neither are names that aren't in the stylesheet:
+///
+/// ```compile_fail
+/// # use html::{div, Div};
+/// # use silkenweb::{css, dom::Dry, prelude::*, typed_classes};
+/// css!(content = ".panel {}");
+///
+/// let panel: Div<Dry> = div().classes(typed_classes!(class, [PANNEL]));
+/// ```
+///
+/// [`css!`]: crate::css
+/// [`Element::classes`]: crate::node::element::Element::classes
+#[macro_export]
+macro_rules! typed_classes {
+    ($module:path, [$($class:ident),* $(,)?]) => {
+        [$({
+            use $module as module;
+            module::$class
+        }),*]
+    };
+    ($module:path, [$($class:ident()),* $(,)?]) => {
+        [$({
+            use $module as module;
+            module::$class()
+        }),*]
+    };
+}
+
+/// A list of CSS classes that's interned and deduplicated once per thread.
+///
+/// The classes must be `&'static str` constants, such as the ones generated by
+/// [`css!`], rather than local variables. The list is built the first time the
+/// expression is evaluated on each thread, so later uses don't do any
+/// interning or duplicate checking work of their own. Pass it to
+/// [`Element::classes`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use silkenweb::{class_set, css, elements::html::{div, Div}, prelude::*};
+/// css!(content = ".button { color: black } .primary { color: blue }");
+///
+/// # let d: Div =
+/// div().classes(class_set![class::BUTTON, class::PRIMARY, class::BUTTON]);
+/// ```
+///
+/// [`css!`]: crate::css
+/// [`Element::classes`]: crate::node::element::Element::classes
+#[macro_export]
+macro_rules! class_set {
+    ($($class:expr),* $(,)?) => {{
+        ::std::thread_local! {
+            static CLASSES: &'static [&'static str] =
+                $crate::macros::intern_class_set(&[$($class),*]);
+        }
+
+        CLASSES.with(|classes| *classes)
+    }};
+}
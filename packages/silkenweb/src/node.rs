@@ -10,10 +10,12 @@ use crate::dom::{
 };
 
 mod component;
+mod fragment;
 
 pub mod element;
 
 pub use component::Component;
+pub use fragment::Fragment;
 
 /// A DOM Node
 pub struct Node<D: Dom = DefaultDom> {
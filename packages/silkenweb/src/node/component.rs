@@ -19,6 +19,8 @@ use crate::{
 /// generate a slot name and assign it to their arguments. They return a
 /// slot, which should be added to the shadow DOM with the
 /// [`child`][`Self::child`] or [`children`][`Self::children`] methods.
+/// [`default_slot`][`Self::default_slot`] adds children to the light DOM
+/// without a slot name, so they're shown in the default slot.
 ///
 /// # Example
 ///
@@ -96,6 +98,19 @@ impl<D: InstantiableDom> Component<D> {
         slot().name(id)
     }
 
+    /// Add `children` to the light DOM, without assigning them to a named
+    /// slot.
+    ///
+    /// They will be shown in the returned unnamed slot, which is the default
+    /// slot for the component. There should only be one default slot.
+    pub fn default_slot<N>(&mut self, children: impl IntoIterator<Item = N>) -> Slot<D>
+    where
+        N: ChildNode<D>,
+    {
+        self.element = Some(self.element.take().unwrap().children(children));
+        slot()
+    }
+
     /// Add `child` to the shadow DOM.
     pub fn child(self, child: impl ChildNode<D>) -> Self {
         Self {
@@ -119,6 +134,17 @@ impl<D: InstantiableDom> Component<D> {
         }
     }
 
+    /// Export parts from the shadow DOM, so they can be styled from outside.
+    ///
+    /// Elements in the shadow DOM are given part names with
+    /// [`HtmlElement::parts`]. See [`HtmlElement::export_parts`] for details.
+    pub fn export_parts<'a>(self, parts: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        Self {
+            element: self.element.map(|elem| elem.export_parts(parts)),
+            id: self.id,
+        }
+    }
+
     fn new_id(&mut self) -> String {
         let id = self.id.to_string();
         self.id += 1;
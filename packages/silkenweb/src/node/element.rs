@@ -4,40 +4,51 @@ use std::collections::HashSet;
 use std::{
     self,
     cell::{Cell, RefCell},
+    collections::HashMap,
     fmt,
-    future::Future,
+    future::{self, Future},
+    hash::Hash,
     marker::PhantomData,
     pin::Pin,
-    rc::Rc,
+    rc::{Rc, Weak},
+    time::Duration,
 };
 
 use discard::DiscardOnDrop;
+use futures::{
+    channel::{mpsc, oneshot},
+    StreamExt,
+};
 use futures_signals::{
     cancelable_future,
-    signal::{Signal, SignalExt},
-    signal_vec::{always, SignalVec, SignalVecExt},
+    signal::{Mutable, ReadOnlyMutable, Signal, SignalExt},
+    signal_vec::{always, MutableVec, SignalVec, SignalVecExt},
     CancelableFutureHandle,
 };
 use silkenweb_base::{clone, document};
-use silkenweb_signals_ext::value::{Executor, RefSignalOrValue, SignalOrValue, Value};
-use wasm_bindgen::{JsCast, JsValue};
+use silkenweb_signals_ext::value::{Executor, RefSignalOrValue, Sig, SignalOrValue, Val, Value};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
-use self::child_vec::ChildVec;
+use self::{child_vec::ChildVec, keyed_children::KeyedChildren};
 use super::{ChildNode, Node, ResourceVec};
 use crate::{
+    animation::throttle_to_frame,
     attribute::Attribute,
+    document::Document,
     dom::{
         private::{DomElement, DomText, EventStore, InstantiableDomElement},
-        DefaultDom, Dom, Hydro, InDom, InstantiableDom, Template, Wet,
+        DefaultDom, Dom, Dry, Hydro, InDom, InstantiableDom, Template, Wet,
     },
+    elements::HtmlElement,
     empty_str,
     hydration::HydrationStats,
-    intern_str,
+    intern_dynamic_str, intern_str,
     node::text,
-    task,
+    task, time, window,
 };
 
 mod child_vec;
+mod keyed_children;
 
 /// A generic HTML element.
 ///
@@ -53,6 +64,8 @@ pub struct GenericElement<D: Dom = DefaultDom, Mutability = Mut> {
     element: D::Element,
     #[cfg(debug_assertions)]
     attributes: HashSet<String>,
+    #[cfg(debug_assertions)]
+    has_inner_html: bool,
     phantom: PhantomData<Mutability>,
 }
 
@@ -73,6 +86,8 @@ impl<D: Dom> GenericElement<D> {
             element: self.element,
             #[cfg(debug_assertions)]
             attributes: self.attributes,
+            #[cfg(debug_assertions)]
+            has_inner_html: self.has_inner_html,
             phantom: PhantomData,
         }
     }
@@ -86,6 +101,8 @@ impl<D: Dom> GenericElement<D> {
             element,
             #[cfg(debug_assertions)]
             attributes: HashSet::new(),
+            #[cfg(debug_assertions)]
+            has_inner_html: false,
             phantom: PhantomData,
         }
     }
@@ -102,6 +119,14 @@ impl<D: Dom> GenericElement<D> {
         let _ = name;
     }
 
+    fn check_no_inner_html(&self) {
+        #[cfg(debug_assertions)]
+        debug_assert!(
+            !self.has_inner_html,
+            "Children can't be added to an element with `dangerous_inner_html`"
+        );
+    }
+
     fn class_signal<T>(
         element: &mut D::Element,
         class: T,
@@ -148,6 +173,22 @@ impl<D: Dom> GenericElement<D> {
 }
 
 impl<D: Dom, Mutability> GenericElement<D, Mutability> {
+    /// The namespace the element was created in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use silkenweb::{
+    /// #     dom::Dry,
+    /// #     node::element::{GenericElement, Namespace},
+    /// # };
+    /// let elem: GenericElement<Dry> = GenericElement::new(Namespace::Svg, "circle");
+    /// assert_eq!(elem.namespace(), Namespace::Svg);
+    /// ```
+    pub fn namespace(&self) -> Namespace {
+        self.element.namespace()
+    }
+
     fn build(&mut self) {
         if let Some(children) = self.child_vec.take() {
             let child_vec = Rc::new(RefCell::new(ChildVec::new(
@@ -186,6 +227,13 @@ where
         self.element.on_instantiate(f);
         self
     }
+
+    pub fn child_on_instantiate<N>(self, f: impl 'static + Fn(&Param) -> Option<N>) -> Self
+    where
+        N: Into<Node<D>>,
+    {
+        self.on_instantiate(move |elem, param| elem.optional_child(f(param).map(N::into)))
+    }
 }
 
 impl<D: Dom> ParentElement<D> for GenericElement<D> {
@@ -193,6 +241,8 @@ impl<D: Dom> ParentElement<D> for GenericElement<D> {
     where
         T: 'a + AsRef<str> + Into<String>,
     {
+        self.check_no_inner_html();
+
         if self.child_vec.is_some() {
             return self.child(child.map(|child| text(child.as_ref())));
         }
@@ -221,6 +271,8 @@ impl<D: Dom> ParentElement<D> for GenericElement<D> {
     }
 
     fn optional_child(self, child: impl SignalOrValue<Item = Option<impl ChildNode<D>>>) -> Self {
+        self.check_no_inner_html();
+
         child.select(
             |mut parent, child| {
                 if let Some(child) = child {
@@ -252,6 +304,8 @@ impl<D: Dom> ParentElement<D> for GenericElement<D> {
     where
         N: Into<Node<D>>,
     {
+        self.check_no_inner_html();
+
         if self.child_vec.is_some() {
             let children = children
                 .into_iter()
@@ -271,6 +325,7 @@ impl<D: Dom> ParentElement<D> for GenericElement<D> {
     where
         N: Into<Node<D>>,
     {
+        self.check_no_inner_html();
         let new_children = children.map(|child| child.into());
 
         let boxed_children = if let Some(child_vec) = self.child_vec.take() {
@@ -283,6 +338,35 @@ impl<D: Dom> ParentElement<D> for GenericElement<D> {
 
         self
     }
+
+    fn dangerous_inner_html<'a>(
+        mut self,
+        html: impl RefSignalOrValue<'a, Item = impl AsRef<str> + 'a>,
+    ) -> Self {
+        assert!(
+            self.static_child_count == 0 && self.child_vec.is_none(),
+            "`dangerous_inner_html` can't be used on an element with children"
+        );
+
+        #[cfg(debug_assertions)]
+        self.has_inner_html = true;
+
+        html.for_each(
+            |elem, html| elem.element.inner_html(html.as_ref()),
+            |elem| {
+                let mut element = elem.element.clone();
+
+                move |new_value| {
+                    element.inner_html(new_value.as_ref());
+
+                    async {}
+                }
+            },
+            &mut self,
+        );
+
+        self
+    }
 }
 
 impl<Mutability> GenericElement<Wet, Mutability> {
@@ -307,6 +391,8 @@ impl<Mutability> GenericElement<Hydro, Mutability> {
             element: self.element.hydrate(element, tracker),
             #[cfg(debug_assertions)]
             attributes: self.attributes,
+            #[cfg(debug_assertions)]
+            has_inner_html: self.has_inner_html,
             phantom: PhantomData,
         }
     }
@@ -384,6 +470,7 @@ impl<D: Dom> Element for GenericElement<D> {
         value: impl RefSignalOrValue<'a, Item = impl Attribute>,
     ) -> Self {
         self.check_attribute_unique(name);
+        let name = intern_dynamic_str(name);
 
         value.for_each(
             |elem, value| elem.element.attribute(name, value),
@@ -464,8 +551,28 @@ impl<D: Dom> Element for GenericElement<D> {
         self
     }
 
-    fn on(mut self, name: &'static str, f: impl FnMut(JsValue) + 'static) -> Self {
-        self.element.on(name, f, &mut self.events);
+    fn keep_alive(mut self, resource: impl 'static) -> Self {
+        self.resources.push(Box::new(resource));
+        self
+    }
+
+    fn on(self, name: &'static str, f: impl FnMut(JsValue) + 'static) -> Self {
+        self.on_with_options(name, EventListenerOptions::default(), f)
+    }
+
+    fn on_with_options(
+        mut self,
+        name: &'static str,
+        options: EventListenerOptions,
+        f: impl FnMut(JsValue) + 'static,
+    ) -> Self {
+        self.element.on(name, options, f, &mut self.events);
+        self
+    }
+
+    fn shrink_to_fit(mut self) -> Self {
+        self.resources.shrink_to_fit();
+        self.events.shrink_to_fit();
         self
     }
 }
@@ -495,6 +602,60 @@ impl<D: Dom, Mutability> From<GenericElement<D, Mutability>> for Node<D> {
     }
 }
 
+/// Convert a [`Node`] back into an element.
+///
+/// This fails if the node isn't an element, for example, if it's a text node.
+/// The original node is returned as the error, so nothing is lost.
+///
+/// ```
+/// # use html::div;
+/// # use silkenweb::{
+/// #     dom::Dry,
+/// #     node::{
+/// #         element::{Const, GenericElement},
+/// #         text, Node,
+/// #     },
+/// #     prelude::*,
+/// # };
+/// let node: Node<Dry> = div().id("my-div").into();
+/// let elem = GenericElement::<Dry, Const>::try_from(node).ok().unwrap();
+/// assert_eq!(elem.to_string(), r#"<div id="my-div"></div>"#);
+///
+/// let node: Node<Dry> = text("Hello!").into();
+/// assert!(GenericElement::<Dry, Const>::try_from(node).is_err());
+/// ```
+impl<D: Dom> TryFrom<Node<D>> for GenericElement<D, Const> {
+    type Error = Node<D>;
+
+    fn try_from(node: Node<D>) -> Result<Self, Self::Error> {
+        let Node {
+            node,
+            resources,
+            events,
+        } = node;
+
+        match D::try_into_element(node) {
+            Ok(element) => Ok(Self {
+                static_child_count: 0,
+                child_vec: None,
+                resources,
+                events,
+                element,
+                #[cfg(debug_assertions)]
+                attributes: HashSet::new(),
+                #[cfg(debug_assertions)]
+                has_inner_html: false,
+                phantom: PhantomData,
+            }),
+            Err(node) => Err(Node {
+                node,
+                resources,
+                events,
+            }),
+        }
+    }
+}
+
 /// Trait alias for elements that can be used as a child
 pub trait ChildElement<D: Dom = DefaultDom>:
     Into<GenericElement<D, Const>> + Into<Node<D>> + Value + 'static
@@ -582,6 +743,9 @@ pub trait Element: Sized {
     /// [`Self::class`], otherwise the results are undefined. Any class signal
     /// values, past or present, must be unique w.r.t. other invocations.
     ///
+    /// Static classes are added one at a time, straight from the iterator, so
+    /// nothing is allocated for `&'static str` classes on [`Wet`] DOM.
+    ///
     /// # Panics
     ///
     /// Panics if any of the items in `classes` contain whitespace.
@@ -629,13 +793,103 @@ pub trait Element: Sized {
     /// wrapped in the [`Sig`] newtype.`Option<impl Attribute>` can be used to
     /// add/remove an attribute based on a signal.
     ///
+    /// Every value the signal yields is written to the DOM, even if it's the
+    /// same as the previous value. This matters for attributes where the
+    /// browser, or a web component's `attributeChangedCallback`, does
+    /// something each time the attribute is set. If writing the same value
+    /// again is wasted work, use [`dedupe`] or [`dedupe_cloned`] on the
+    /// signal to skip unchanged values:
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let title = Mutable::new("Home".to_string());
+    ///
+    /// # let d: Div =
+    /// div().attribute("title", Sig(title.signal_cloned().dedupe_cloned()));
+    /// ```
+    ///
     /// [`Sig`]: crate::value::Sig
+    /// [`dedupe`]: futures_signals::signal::SignalExt::dedupe
+    /// [`dedupe_cloned`]: futures_signals::signal::SignalExt::dedupe_cloned
     fn attribute<'a>(
         self,
         name: &str,
         value: impl RefSignalOrValue<'a, Item = impl Attribute>,
     ) -> Self;
 
+    /// Set a [`data-*`] attribute.
+    ///
+    /// `name` is the part after `data-`. Otherwise, this is the same as
+    /// [`Self::attribute`].
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if `name` isn't a hyphen separated list of
+    /// lowercase ASCII words. For example, `"user-id"` is OK, but `"userId"`
+    /// and `"user--id"` aren't.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*};
+    /// let app: Div<Dry> = div().data_attribute("user-id", "1234");
+    /// assert_eq!(
+    ///     app.freeze().to_string(),
+    ///     r#"<div data-user-id="1234"></div>"#
+    /// );
+    /// ```
+    ///
+    /// [`data-*`]: https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/data-*
+    fn data_attribute<'a>(
+        self,
+        name: &str,
+        value: impl RefSignalOrValue<'a, Item = impl Attribute>,
+    ) -> Self {
+        debug_assert!(
+            is_valid_data_attribute_name(name),
+            "Invalid data attribute name \"{name}\": it should be lowercase words separated by \
+             hyphens"
+        );
+
+        self.attribute(&format!("data-{name}"), value)
+    }
+
+    /// Add or remove an attribute whose presence is what matters.
+    ///
+    /// When `present` is `true`, the attribute is set to an empty value, and
+    /// when it's `false` the attribute is removed. This is the same as
+    /// passing a `bool` to [`Self::attribute`], but makes the intent clear at
+    /// the call site.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let hidden = Mutable::new(true);
+    /// let app: Div<Dry> = div().toggle_attribute("aria-hidden", Sig(hidden.signal()));
+    /// let app = app.freeze();
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<div aria-hidden=""></div>"#);
+    ///
+    /// hidden.set(false);
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<div></div>"#);
+    /// # }))
+    /// ```
+    fn toggle_attribute<'a>(
+        self,
+        name: &str,
+        present: impl RefSignalOrValue<'a, Item = bool>,
+    ) -> Self {
+        self.attribute(name, present)
+    }
+
     /// Set an inline style property
     ///
     /// The property can be a value or a signal. Signals should be wrapped in
@@ -697,132 +951,1491 @@ pub trait Element: Sized {
 
     /// Spawn a future on the element.
     ///
-    /// The future will be dropped when this element is dropped.
+    /// The future will be dropped when this element is dropped. Elements are
+    /// dropped when they're removed from their parent, for example by a
+    /// [`ParentElement::children_signal`] update, so long running futures
+    /// don't outlive the element they're spawned on. The future is cancelled
+    /// straight away, but it's dropped from the microtask queue.
     fn spawn_future(self, future: impl Future<Output = ()> + 'static) -> Self;
 
-    /// Register an event handler.
+    /// Spawn a future on the element, and set `value` to its output.
     ///
-    /// `name` is the name of the event. See the [MDN Events] page for a list.
+    /// Like [`Element::spawn_future`], the future will be dropped when this
+    /// element is dropped, in which case `value` won't be set.
     ///
-    /// `f` is the callback when the event fires and will be passed the
-    /// javascript `Event` object.
+    /// # Example
     ///
-    /// [MDN Events]: https://developer.mozilla.org/en-US/docs/Web/Events
-    fn on(self, name: &'static str, f: impl FnMut(JsValue) + 'static) -> Self;
-}
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let text = Mutable::new("Loading...".to_string());
+    /// let app: Div<Dry> = div()
+    ///     .text(Sig(text.signal_cloned()))
+    ///     .spawn_future_into(async { "Loaded".to_string() }, text);
+    /// let app = app.freeze();
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), "<div>Loaded</div>");
+    /// # }))
+    /// ```
+    fn spawn_future_into<T>(
+        self,
+        future: impl Future<Output = T> + 'static,
+        value: Mutable<T>,
+    ) -> Self
+    where
+        T: 'static,
+    {
+        self.spawn_future(async move { value.set(future.await) })
+    }
 
-/// An element that can have children.
-pub trait ParentElement<D: Dom = DefaultDom>: Element {
-    /// Add a text child to this element
+    /// Keep `resource` alive until the element is dropped.
+    ///
+    /// This is for things like observers, that are set up in an
+    /// [`Element::effect`] and should stop when the element is removed from
+    /// the DOM. Like futures spawned with [`Element::spawn_future`], `resource`
+    /// is dropped when the element is dropped.
+    ///
+    /// The default implementation holds `resource` in a future spawned on the
+    /// element. Elements in this crate override it to store `resource`
+    /// directly.
+    fn keep_alive(self, resource: impl 'static) -> Self {
+        self.spawn_future(async move {
+            let _resource = resource;
+            future::pending().await
+        })
+    }
+
+    /// Call `f` once this element is mounted.
     ///
-    /// # Example
+    /// `f` is called after the next render, like [`Element::effect`], so the
+    /// element is in the document by then. Use [`Element::effect`] if `f`
+    /// needs the DOM element. As with effects, `f` isn't called for [`Dry`]
+    /// elements, or [`Hydro`] elements until they're hydrated.
     ///
-    /// Static text:
+    /// # Example
     ///
     /// ```no_run
     /// # use html::{div, Div};
     /// # use silkenweb::prelude::*;
+    /// let is_mounted = Mutable::new(false);
     /// # let d: Div =
-    /// div().text("Hello, world!");
+    /// div().on_mount(move || is_mounted.set(true));
     /// ```
+    fn on_mount(self, f: impl FnOnce() + 'static) -> Self {
+        self.effect(|_| f())
+    }
+
+    /// Call `f` when this element is unmounted.
     ///
-    /// Dynamic text:
+    /// An element is unmounted when it's dropped, which happens when it's
+    /// removed from its parent. [`MountHandle::unmount`] removes the element
+    /// from the document, but returns it as an [`Unmounted`], so it can be put
+    /// back with [`Unmounted::remount`]. `f` isn't called until that
+    /// [`Unmounted`] is dropped.
+    ///
+    /// This is useful for cleaning up resources that aren't owned by the
+    /// element, such as a chart from a JavaScript library. Use
+    /// [`Element::effect`] to set them up once the element is rendered.
+    ///
+    /// `f` is called as the element's resources are dropped, along with the
+    /// futures spawned with [`Element::spawn_future`]. They're dropped in an
+    /// unspecified order, so `f` shouldn't depend on them.
+    ///
+    /// # Example
     ///
     /// ```no_run
     /// # use html::{div, Div};
     /// # use silkenweb::prelude::*;
-    /// let text = Mutable::new("Hello, world!");
+    /// let is_mounted = Mutable::new(true);
     /// # let d: Div =
-    /// div().text(Sig(text.signal()));
+    /// div().on_unmount(move || is_mounted.set(false));
     /// ```
-    fn text<'a, T>(self, child: impl RefSignalOrValue<'a, Item = T>) -> Self
-    where
-        T: 'a + AsRef<str> + Into<String>;
+    ///
+    /// [`MountHandle::unmount`]: crate::document::MountHandle::unmount
+    /// [`Unmounted`]: crate::document::Unmounted
+    /// [`Unmounted::remount`]: crate::document::Unmounted::remount
+    fn on_unmount(self, f: impl FnOnce() + 'static) -> Self {
+        self.keep_alive(OnDrop(Some(f)))
+    }
 
-    /// Add a child to the element.
+    /// Register an event handler.
     ///
-    /// # Example
+    /// `name` is the name of the event. See the [MDN Events] page for a list.
     ///
-    /// Add static children:
+    /// `f` is the callback when the event fires and will be passed the
+    /// javascript `Event` object.
     ///
-    /// ```no_run
-    /// # use html::{div, p, Div};
-    /// # use silkenweb::prelude::*;
-    /// # let div: Div =
-    /// div().child(p().text("Hello,")).child(p().text("world!"));
-    /// ```
+    /// [MDN Events]: https://developer.mozilla.org/en-US/docs/Web/Events
+    fn on(self, name: &'static str, f: impl FnMut(JsValue) + 'static) -> Self;
+
+    /// Register an event handler with `options`.
     ///
-    /// Add a dynamic child:
+    /// This is like [`Element::on`], but `options` can make the handler
+    /// passive, capture events, or only be called once. Each event also has
+    /// an `on_<event>_with_options` method, such as
+    /// [`ElementEvents::on_click_with_options`].
+    ///
+    /// # Example
     ///
     /// ```no_run
     /// # use html::{div, Div};
-    /// # use silkenweb::prelude::*;
-    /// let text = Mutable::new("Hello, world!");
-    ///
+    /// # use silkenweb::{node::element::EventListenerOptions, prelude::*};
     /// # let d: Div =
-    /// div().child(Sig(text.signal().map(|text| div().text(text))));
+    /// div().on_with_options(
+    ///     "touchstart",
+    ///     EventListenerOptions {
+    ///         passive: true,
+    ///         ..EventListenerOptions::default()
+    ///     },
+    ///     |_| (),
+    /// );
     /// ```
-    fn child(self, child: impl SignalOrValue<Item = impl ChildNode<D>>) -> Self {
-        self.optional_child(child.map(|child| Some(child)))
+    ///
+    /// The default implementation ignores `options` and calls
+    /// [`Element::on`], so types outside this crate that implement [`Element`]
+    /// should override it if they can apply the options.
+    ///
+    /// [`ElementEvents::on_click_with_options`]: crate::elements::ElementEvents::on_click_with_options
+    fn on_with_options(
+        self,
+        name: &'static str,
+        options: EventListenerOptions,
+        f: impl FnMut(JsValue) + 'static,
+    ) -> Self {
+        let _ = options;
+        self.on(name, f)
     }
 
-    /// Add an optional child to the element.
+    /// Release any spare capacity held for this element and its children.
     ///
-    /// The child will update when the signal changes to `Some(..)`, and will be
-    /// removed when the signal changes to `None`.
+    /// Element trees are built incrementally, so the lists of futures and
+    /// event handlers they keep may have more capacity than they need. Call
+    /// this on the root of a large tree, once it's built, to compact them.
+    /// Children added to an element are combined with it, so this covers the
+    /// whole tree. Children added later, for example from a signal, aren't
+    /// affected.
     ///
-    /// # Example
+    /// Event handlers are only kept for DOM elements, so this has nothing to
+    /// compact for [`Dry`] elements, or [`Hydro`] elements that haven't been
+    /// hydrated.
     ///
-    /// Add a static optional child:
+    /// The default implementation does nothing.
+    fn shrink_to_fit(self) -> Self {
+        self
+    }
+
+    /// Set the [`will-change`] CSS property while `during` is `true`.
     ///
-    /// ```no_run
-    /// # use html::{div, p, Div};
-    /// # use silkenweb::prelude::*;
-    /// let text = Mutable::new("hello");
+    /// `will-change` hints to the browser that `properties` are about to be
+    /// animated. Leaving it set permanently can use a lot of memory, so it's
+    /// removed whenever `during` is `false`.
+    ///
+    /// # Example
     ///
-    /// # let div: Div =
-    /// div().optional_child(Some(p().text("Hello, world!")));
     /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let animating = Mutable::new(true);
+    /// let app: Div<Dry> = div().will_change("transform", animating.signal());
+    /// let app = app.freeze();
     ///
-    /// Add a dynamic optional child:
+    /// render_now().await;
+    /// assert_eq!(
+    ///     app.to_string(),
+    ///     r#"<div style="will-change: transform;"></div>"#
+    /// );
     ///
-    /// ```no_run
-    /// # use html::{div, Div};
-    /// # use silkenweb::prelude::*;
-    /// let text = Mutable::new("hello");
+    /// animating.set(false);
     ///
-    /// # let div: Div =
-    /// div().optional_child(Sig(text.signal().map(|text| {
-    ///     if text.is_empty() {
-    ///         None
-    ///     } else {
-    ///         Some(div().text(text))
-    ///     }
-    /// })));
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), "<div></div>");
+    /// # }))
     /// ```
-    fn optional_child(self, child: impl SignalOrValue<Item = Option<impl ChildNode<D>>>) -> Self;
+    ///
+    /// [`will-change`]: https://developer.mozilla.org/en-US/docs/Web/CSS/will-change
+    fn will_change(
+        self,
+        properties: impl Into<String>,
+        during: impl Signal<Item = bool> + 'static,
+    ) -> Self {
+        let properties = properties.into();
+
+        self.style_property(
+            "will-change",
+            Sig(during.map(move |active| {
+                if active {
+                    properties.clone()
+                } else {
+                    String::new()
+                }
+            })),
+        )
+    }
 
-    /// Add children to the element.
+    /// Set [`aria-busy`] while `loading` is `true`.
+    ///
+    /// This lets screen readers know that a region is still loading. The
+    /// attribute is removed when `loading` is `false`.
     ///
     /// # Example
     ///
-    /// ```no_run
-    /// # use html::{div, p, Div};
-    /// # use silkenweb::prelude::*;
-    /// # let div: Div =
-    /// div().children([p().text("Hello,"), p().text("world!")]);
     /// ```
-    fn children<N>(self, children: impl IntoIterator<Item = N>) -> Self
-    where
-        N: Into<Node<D>>;
-
-    /// Add children from a [`SignalVec`] to the element.
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let loading = Mutable::new(true);
+    /// let app: Div<Dry> = div().aria_busy_while(loading.signal());
+    /// let app = app.freeze();
     ///
-    /// See [counter_list](https://github.com/silkenweb/silkenweb/tree/main/examples/counter-list/src/main.rs)
-    /// for an example
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<div aria-busy="true"></div>"#);
+    ///
+    /// loading.set(false);
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), "<div></div>");
+    /// # }))
+    /// ```
+    ///
+    /// [`aria-busy`]: https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-busy
+    fn aria_busy_while(self, loading: impl Signal<Item = bool> + 'static) -> Self {
+        self.attribute(
+            "aria-busy",
+            Sig(loading.map(|loading| loading.then_some("true"))),
+        )
+    }
+
+    /// Set an attribute, applying updates on animation frames.
+    ///
+    /// This is like [`Self::attribute`], but updates from a signal are
+    /// collapsed with [`throttle_to_frame`], so the attribute is set at most
+    /// once per animation frame. All deferred attributes and style properties
+    /// that change in a frame are set together, so the browser only has to
+    /// recalculate layout once. This is useful for layout affecting
+    /// attributes that are computed from several frequently changing
+    /// signals. The initial value is set straight away.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let rows = Mutable::new(1);
+    /// let app: Div<Dry> = div().defer_attribute("data-rows", Sig(rows.signal()));
+    /// let app = app.freeze();
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<div data-rows="1"></div>"#);
+    ///
+    /// rows.set(2);
+    /// rows.set(3);
+    ///
+    /// // The first `render_now` runs the animation frame, and the second applies
+    /// // the update.
+    /// render_now().await;
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<div data-rows="3"></div>"#);
+    /// # }))
+    /// ```
+    ///
+    /// [`throttle_to_frame`]: crate::animation::throttle_to_frame
+    fn defer_attribute<T>(self, name: &str, value: impl SignalOrValue<Item = T>) -> Self
+    where
+        T: Attribute + 'static,
+    {
+        value.select(
+            |(elem, name), value| elem.attribute(name, Val(value)),
+            |(elem, name), value| elem.attribute(name, Sig(throttle_to_frame(value))),
+            (self, name),
+        )
+    }
+
+    /// Set a style property, applying updates on animation frames.
+    ///
+    /// See [`Self::defer_attribute`] for details.
+    fn defer_style_property<T>(
+        self,
+        name: impl Into<String>,
+        value: impl SignalOrValue<Item = T>,
+    ) -> Self
+    where
+        T: AsRef<str> + 'static,
+    {
+        value.select(
+            |(elem, name), value| elem.style_property(name, Val(value)),
+            |(elem, name), value| elem.style_property(name, Sig(throttle_to_frame(value))),
+            (self, name),
+        )
+    }
+
+    /// Bind an attribute to a [`Mutable`] in both directions.
+    ///
+    /// The attribute is set whenever `value` changes, and `value` is updated
+    /// whenever something else, such as a web component, changes the
+    /// attribute. Changes are observed with a [`MutationObserver`], which is
+    /// disconnected when the element is dropped. `value` is only updated if
+    /// the attribute actually changes, so our own writes don't feed back
+    /// into it. If the attribute is removed, `value` is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let selected = Mutable::new("first".to_string());
+    /// # let d: Div =
+    /// div().bind_attribute("data-selected", selected.clone());
+    /// ```
+    ///
+    /// [`MutationObserver`]: https://developer.mozilla.org/en-US/docs/Web/API/MutationObserver
+    fn bind_attribute(self, name: &'static str, value: Mutable<String>) -> Self {
+        let observer = Rc::new(RefCell::new(None));
+
+        self.attribute(name, Sig(value.signal_cloned()))
+            .effect({
+                clone!(observer);
+
+                move |elem| {
+                    let observed =
+                        MutationObserver::attribute(elem.unchecked_ref(), name, move |new_value| {
+                            value.set_neq(new_value)
+                        });
+                    observer.replace(Some(observed));
+                }
+            })
+            .keep_alive(observer)
+    }
+
+    /// Set the [`container-type`] CSS property.
+    ///
+    /// This makes the element a container for [container queries].
+    ///
+    /// [`container-type`]: https://developer.mozilla.org/en-US/docs/Web/CSS/container-type
+    /// [container queries]: https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_container_queries
+    fn container_type(self, container_type: ContainerType) -> Self {
+        self.style_property("container-type", container_type.as_str())
+    }
+
+    /// Set the [`container-name`] CSS property.
+    ///
+    /// `@container` rules can use the name to query this element.
+    ///
+    /// [`container-name`]: https://developer.mozilla.org/en-US/docs/Web/CSS/container-name
+    fn container_name(self, name: impl Into<String>) -> Self {
+        self.style_property("container-name", name.into())
+    }
+
+    /// Call `f` with the width and height of the element's content box,
+    /// whenever it's resized.
+    ///
+    /// This is [`ElementHandle::size_signal`], as a callback. The size is
+    /// observed with a [`ResizeObserver`], which is owned by the element, and
+    /// disconnected when the element is dropped. `f` is also called when the
+    /// element is first rendered. This can be used to style a component based
+    /// on its own size, where container queries aren't supported. On the
+    /// server, `f` is never called.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let is_narrow = Mutable::new(false);
+    /// # let d: Div =
+    /// div()
+    ///     .classes(Sig(is_narrow.signal().map(|narrow| narrow.then_some("narrow"))))
+    ///     .on_resize(move |width, _height| is_narrow.set_neq(width < 400.0));
+    /// ```
+    ///
+    /// [`ResizeObserver`]: https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver
+    fn on_resize(self, mut f: impl FnMut(f64, f64) + 'static) -> Self {
+        let size = ResizeObserver::size_signal::<Self::Dom>(&self.handle().0);
+
+        self.spawn_future(size.for_each(move |size| {
+            if let Some((width, height)) = size {
+                f(width, height);
+            }
+
+            async {}
+        }))
+    }
+
+    /// Set the [`anchor-name`] CSS property.
+    ///
+    /// `name` must be a dashed identifier, like `--menu-button`. Other elements
+    /// can be positioned relative to this one with
+    /// [`Element::position_anchor`].
+    ///
+    /// [`anchor-name`]: https://developer.mozilla.org/en-US/docs/Web/CSS/anchor-name
+    fn anchor_name(self, name: impl Into<String>) -> Self {
+        self.style_property("anchor-name", name.into())
+    }
+
+    /// Position this element on the `side` of an anchor, using [CSS anchor
+    /// positioning].
+    ///
+    /// The anchor should have its [`anchor_name`][Element::anchor_name] set to
+    /// `name`, and `anchor` is a handle to it. This element is given a fixed
+    /// position, so it's useful for popovers, tooltips and menus.
+    ///
+    /// Where anchor positioning isn't supported, the element is positioned in
+    /// JavaScript instead, using the anchor's bounding client rectangle. The
+    /// position is updated when the anchor is resized, or the window is
+    /// resized or scrolled. It's not updated if the anchor is moved for any
+    /// other reason, such as scrolling a container other than the document.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{button, div, Button, Div};
+    /// # use silkenweb::{elements::PopoverMode, node::element::AnchorSide, prelude::*};
+    /// let menu_button: Button = button().anchor_name("--menu-button").text("Menu");
+    /// let menu: Div = div()
+    ///     .popover(PopoverMode::Auto)
+    ///     .position_anchor("--menu-button", menu_button.handle(), AnchorSide::Bottom)
+    ///     .text("Menu items");
+    /// ```
+    ///
+    /// [CSS anchor positioning]: https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_anchor_positioning
+    fn position_anchor<AnchorElement>(
+        self,
+        name: impl Into<String>,
+        anchor: ElementHandle<Self::Dom, AnchorElement>,
+        side: AnchorSide,
+    ) -> Self
+    where
+        AnchorElement: JsCast + Clone + 'static,
+    {
+        let positioner = Rc::new(RefCell::new(None));
+        let anchor = ElementHandle::<Self::Dom, web_sys::Element>(anchor.0, PhantomData);
+        let [(inset, anchor_edge), (cross_inset, cross_edge)] = side.css_insets();
+
+        self.style_property("position", "fixed")
+            .style_property("position-anchor", name.into())
+            .style_property(inset, format!("anchor({anchor_edge})"))
+            .style_property(cross_inset, format!("anchor({cross_edge})"))
+            .effect({
+                let positioner = Rc::downgrade(&positioner);
+
+                move |elem| {
+                    let supported = web_sys::css::supports_with_value("anchor-name", "--anchor")
+                        .unwrap_or(false);
+
+                    if !supported {
+                        AnchorPositioner::attach(
+                            elem.unchecked_ref::<web_sys::HtmlElement>().clone(),
+                            anchor,
+                            side,
+                            positioner,
+                        );
+                    }
+                }
+            })
+            .keep_alive(positioner)
+    }
+}
+
+/// Options for [`Element::on_with_options`].
+///
+/// See [`addEventListener`] on MDN for details of each option.
+///
+/// [`addEventListener`]: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener#options
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct EventListenerOptions {
+    /// The handler won't call `preventDefault`, so the browser doesn't need to
+    /// wait for it before scrolling.
+    pub passive: bool,
+    /// Call the handler in the capture phase, before handlers on descendants.
+    pub capture: bool,
+    /// Only call the handler once.
+    pub once: bool,
+}
+
+impl From<EventListenerOptions> for web_sys::AddEventListenerOptions {
+    fn from(options: EventListenerOptions) -> Self {
+        let mut web_options = Self::new();
+
+        // Only set the options that were asked for, so the browser's defaults
+        // apply to the rest.
+        if options.passive {
+            web_options.passive(true);
+        }
+
+        if options.capture {
+            web_options.capture(true);
+        }
+
+        if options.once {
+            web_options.once(true);
+        }
+
+        web_options
+    }
+}
+
+/// Options for [`ElementHandle::focus_with_options`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct FocusOptions {
+    /// Don't scroll the element into view.
+    pub prevent_scroll: bool,
+    /// Force the focus ring to be shown, or hidden.
+    ///
+    /// With `None`, the browser decides whether to show it.
+    pub focus_visible: Option<bool>,
+}
+
+/// Options for [`ElementHandle::visible_signal_with`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisibilityOptions {
+    /// Grow or shrink the viewport by a CSS margin before checking visibility.
+    ///
+    /// This is the same as the `margin` CSS property, for example `"200px"`
+    /// to count the element as visible when it's within 200px of the
+    /// viewport.
+    pub root_margin: String,
+    /// The fraction of the element that needs to be visible, from `0.0` to
+    /// `1.0`.
+    ///
+    /// With `0.0`, the element is visible if any of it is in the viewport.
+    pub threshold: f64,
+}
+
+impl Default for VisibilityOptions {
+    fn default() -> Self {
+        Self {
+            root_margin: "0px".to_string(),
+            threshold: 0.0,
+        }
+    }
+}
+
+/// Which side of an anchor to put an element.
+///
+/// The element is aligned with the start of the anchor, so `Top` and `Bottom`
+/// align the left edges, and `Left` and `Right` align the top edges.
+///
+/// See [`Element::position_anchor`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AnchorSide {
+    /// Above the anchor.
+    Top,
+    /// Below the anchor.
+    Bottom,
+    /// To the left of the anchor.
+    Left,
+    /// To the right of the anchor.
+    Right,
+}
+
+impl AnchorSide {
+    /// The inset properties to set, and the anchor edges to set them to.
+    fn css_insets(self) -> [(&'static str, &'static str); 2] {
+        match self {
+            Self::Top => [("bottom", "top"), ("left", "left")],
+            Self::Bottom => [("top", "bottom"), ("left", "left")],
+            Self::Left => [("right", "left"), ("top", "top")],
+            Self::Right => [("left", "right"), ("top", "top")],
+        }
+    }
+
+    /// The inset properties to set, given the anchor's bounding client
+    /// rectangle and the viewport size.
+    fn fallback_insets(
+        self,
+        anchor: &web_sys::DomRect,
+        viewport_width: f64,
+        viewport_height: f64,
+    ) -> [(&'static str, f64); 2] {
+        match self {
+            Self::Top => [
+                ("bottom", viewport_height - anchor.top()),
+                ("left", anchor.left()),
+            ],
+            Self::Bottom => [("top", anchor.bottom()), ("left", anchor.left())],
+            Self::Left => [
+                ("right", viewport_width - anchor.left()),
+                ("top", anchor.top()),
+            ],
+            Self::Right => [("left", anchor.right()), ("top", anchor.top())],
+        }
+    }
+}
+
+/// Values for the [`container-type`] CSS property.
+///
+/// See [`Element::container_type`].
+///
+/// [`container-type`]: https://developer.mozilla.org/en-US/docs/Web/CSS/container-type
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ContainerType {
+    /// Not a query container for size queries.
+    Normal,
+    /// A query container for size queries in both dimensions.
+    Size,
+    /// A query container for size queries in the inline dimension.
+    InlineSize,
+}
+
+impl ContainerType {
+    /// The CSS value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Size => "size",
+            Self::InlineSize => "inline-size",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum TransitionPhase {
+    Entering,
+    Present,
+    Leaving,
+}
+
+fn remove_transition_child(ids: &MutableVec<u64>, id: u64) {
+    let mut ids = ids.lock_mut();
+
+    if let Some(index) = ids.iter().position(|x| *x == id) {
+        ids.remove(index);
+    }
+}
+
+/// How long the transitions on `elem` take to finish, from its computed
+/// `transition-duration` and `transition-delay`.
+fn transition_timeout(elem: &web_sys::Element) -> Duration {
+    let style = web_sys::window().and_then(|window| window.get_computed_style(elem).ok().flatten());
+    let style = match style {
+        Some(style) => style,
+        None => return Duration::ZERO,
+    };
+    let times = |property: &str| -> Vec<f64> {
+        style
+            .get_property_value(property)
+            .unwrap_or_default()
+            .split(',')
+            .map(|time| parse_css_time(time.trim()).unwrap_or(0.0))
+            .collect()
+    };
+    let durations = times("transition-duration");
+    let delays = times("transition-delay");
+
+    // Like CSS, repeat the list of delays to match the list of durations.
+    let longest = durations
+        .iter()
+        .zip(delays.iter().cycle())
+        .map(|(duration, delay)| duration + delay)
+        .fold(0.0, f64::max);
+
+    Duration::from_secs_f64(longest)
+}
+
+/// Parse a CSS `<time>`, like `0.3s` or `300ms`, into seconds.
+fn parse_css_time(time: &str) -> Option<f64> {
+    if let Some(millis) = time.strip_suffix("ms") {
+        millis.parse::<f64>().ok().map(|millis| millis / 1000.0)
+    } else {
+        time.strip_suffix('s')?.parse().ok()
+    }
+}
+
+fn is_valid_data_attribute_name(name: &str) -> bool {
+    name.split('-').all(|word| {
+        !word.is_empty()
+            && word
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    })
+}
+
+/// Call a function when dropped.
+struct OnDrop<F: FnOnce()>(Option<F>);
+
+impl<F: FnOnce()> Drop for OnDrop<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.0.take() {
+            f()
+        }
+    }
+}
+
+/// A signal of changes sent to a channel, with no initial value.
+struct Changes(mpsc::UnboundedReceiver<()>);
+
+impl Signal for Changes {
+    type Item = ();
+
+    fn poll_change(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.0.poll_next_unpin(cx)
+    }
+}
+
+pub(crate) struct MutationObserver {
+    observer: web_sys::MutationObserver,
+    _callback: Closure<dyn FnMut()>,
+}
+
+impl MutationObserver {
+    fn new(
+        elem: &web_sys::Element,
+        options: &web_sys::MutationObserverInit,
+        on_mutation: impl FnMut() + 'static,
+    ) -> Self {
+        let callback = Closure::<dyn FnMut()>::new(on_mutation);
+        let observer =
+            web_sys::MutationObserver::new(callback.as_ref().unchecked_ref()).unwrap_throw();
+        observer.observe_with_options(elem, options).unwrap_throw();
+
+        Self {
+            observer,
+            _callback: callback,
+        }
+    }
+
+    fn attribute(
+        elem: &web_sys::Element,
+        name: &'static str,
+        mut on_change: impl FnMut(String) + 'static,
+    ) -> Self {
+        let mut options = web_sys::MutationObserverInit::new();
+        options
+            .attributes(true)
+            .attribute_filter(&js_sys::Array::of1(&JsValue::from_str(name)));
+
+        Self::new(elem, &options, {
+            let elem = elem.clone();
+
+            move || {
+                if let Some(value) = elem.get_attribute(name) {
+                    on_change(value)
+                }
+            }
+        })
+    }
+
+    pub(crate) fn children(elem: &web_sys::Element, on_change: impl FnMut() + 'static) -> Self {
+        let mut options = web_sys::MutationObserverInit::new();
+        options.child_list(true).character_data(true).subtree(true);
+
+        Self::new(elem, &options, on_change)
+    }
+}
+
+impl Drop for MutationObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+struct IntersectionObserver {
+    observer: web_sys::IntersectionObserver,
+    _callback: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl IntersectionObserver {
+    fn new(
+        elem: &web_sys::Element,
+        options: &VisibilityOptions,
+        mut on_change: impl FnMut(bool) + 'static,
+    ) -> Self {
+        let threshold = options.threshold;
+        let callback = Closure::<dyn FnMut(js_sys::Array)>::new(move |entries: js_sys::Array| {
+            // Only one element is observed, so the last entry has the latest state.
+            if let Some(entry) = entries.iter().last() {
+                let entry: web_sys::IntersectionObserverEntry = entry.unchecked_into();
+                on_change(entry.is_intersecting() && entry.intersection_ratio() >= threshold)
+            }
+        });
+        let mut init = web_sys::IntersectionObserverInit::new();
+        init.root_margin(&options.root_margin)
+            .threshold(&JsValue::from_f64(threshold));
+        let observer = web_sys::IntersectionObserver::new_with_options(
+            callback.as_ref().unchecked_ref(),
+            &init,
+        )
+        .unwrap_throw();
+        observer.observe(elem);
+
+        Self {
+            observer,
+            _callback: callback,
+        }
+    }
+}
+
+impl Drop for IntersectionObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+struct ResizeObserver {
+    observer: js::ResizeObserver,
+    _callback: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl ResizeObserver {
+    fn new(elem: &web_sys::Element, mut on_resize: impl FnMut(f64, f64) + 'static) -> Self {
+        let callback = Closure::<dyn FnMut(js_sys::Array)>::new(move |entries: js_sys::Array| {
+            // Only one element is observed, so the last entry has the latest size.
+            if let Some(entry) = entries.iter().last() {
+                let rect = entry
+                    .unchecked_into::<js::ResizeObserverEntry>()
+                    .content_rect();
+                on_resize(rect.width(), rect.height())
+            }
+        });
+        let observer = js::ResizeObserver::new(callback.as_ref().unchecked_ref());
+        observer.observe(elem);
+
+        Self {
+            observer,
+            _callback: callback,
+        }
+    }
+}
+
+impl ResizeObserver {
+    /// A signal of the size of `elem`'s content box, once it's been observed.
+    ///
+    /// The observer is created once `elem` is rendered, and disconnected when
+    /// the signal is dropped. The size is `None` until it's first observed.
+    fn size_signal<D: Dom>(elem: &D::Element) -> impl Signal<Item = Option<(f64, f64)>> + 'static {
+        let size = Mutable::new(None);
+        let observer = Rc::new(RefCell::new(None));
+
+        elem.clone().effect({
+            clone!(size, observer);
+
+            move |elem| {
+                observer.replace(Some(ResizeObserver::new(elem, move |width, height| {
+                    size.set_neq(Some((width, height)))
+                })));
+            }
+        });
+
+        size.signal().map(move |size| {
+            // Keep the observer alive until the signal is dropped.
+            let _observer = &observer;
+            size
+        })
+    }
+}
+
+impl Drop for ResizeObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// Positions an element next to an anchor, for browsers without CSS anchor
+/// positioning.
+struct AnchorPositioner {
+    _anchor_observer: ResizeObserver,
+    _on_resize: window::EventCallback,
+    _on_scroll: window::EventCallback,
+}
+
+impl AnchorPositioner {
+    /// Position `elem` relative to `anchor`, and store the positioner in `slot`.
+    ///
+    /// If `anchor` doesn't have a DOM element yet, for example because it
+    /// hasn't been hydrated, try again on the next animation frame. Stop once
+    /// `slot` has been dropped along with its element.
+    fn attach<D: Dom>(
+        elem: web_sys::HtmlElement,
+        anchor: ElementHandle<D, web_sys::Element>,
+        side: AnchorSide,
+        slot: Weak<RefCell<Option<Self>>>,
+    ) {
+        let positioner = match slot.upgrade() {
+            Some(positioner) => positioner,
+            None => return,
+        };
+
+        if let Some(anchor_elem) = anchor.try_dom_element() {
+            positioner.replace(Some(Self::new(&elem, anchor_elem, side)));
+        } else {
+            task::on_animation_frame(move || Self::attach(elem, anchor, side, slot));
+        }
+    }
+
+    fn new(elem: &web_sys::HtmlElement, anchor: web_sys::Element, side: AnchorSide) -> Self {
+        let position: Rc<dyn Fn()> = Rc::new({
+            clone!(anchor);
+            let style = elem.style();
+
+            move || {
+                let rect = anchor.get_bounding_client_rect();
+                let (viewport_width, viewport_height) = anchor
+                    .owner_document()
+                    .and_then(|doc| doc.document_element())
+                    .map_or((0.0, 0.0), |root| {
+                        (root.client_width().into(), root.client_height().into())
+                    });
+
+                for (inset, value) in side.fallback_insets(&rect, viewport_width, viewport_height) {
+                    style
+                        .set_property(inset, &format!("{value}px"))
+                        .unwrap_throw();
+                }
+            }
+        });
+
+        // The observer calls `position` when it starts observing, so the element is
+        // positioned straight away.
+        Self {
+            _anchor_observer: ResizeObserver::new(&anchor, {
+                clone!(position);
+                move |_, _| position()
+            }),
+            _on_resize: window::on_resize({
+                clone!(position);
+                move |_| position()
+            }),
+            _on_scroll: window::on_scroll(move |_| position()),
+        }
+    }
+}
+
+// `ResizeObserver` is an unstable API in `web-sys`, so we define the parts we
+// need.
+mod js {
+    use wasm_bindgen::prelude::wasm_bindgen;
+
+    #[wasm_bindgen]
+    extern "C" {
+        pub type ResizeObserver;
+
+        #[wasm_bindgen(constructor)]
+        pub fn new(callback: &js_sys::Function) -> ResizeObserver;
+
+        #[wasm_bindgen(method)]
+        pub fn observe(this: &ResizeObserver, target: &web_sys::Element);
+
+        #[wasm_bindgen(method)]
+        pub fn disconnect(this: &ResizeObserver);
+
+        pub type ResizeObserverEntry;
+
+        #[wasm_bindgen(method, getter, js_name = contentRect)]
+        pub fn content_rect(this: &ResizeObserverEntry) -> web_sys::DomRectReadOnly;
+    }
+}
+
+/// An element that can have children.
+///
+/// # Child Order
+///
+/// Children are always kept in the order their methods were called, whether
+/// they're static or come from signals. Each dynamic group of children, from
+/// [`optional_child`][Self::optional_child] or
+/// [`children_signal`][Self::children_signal] for example, occupies its own
+/// contiguous range, which grows and shrinks without disturbing the children
+/// before or after it. Widgets that manage several reactive collections can
+/// rely on this, rather than tracking DOM positions themselves.
+pub trait ParentElement<D: Dom = DefaultDom>: Element {
+    /// Add a text child to this element
+    ///
+    /// # Example
+    ///
+    /// Static text:
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// # let d: Div =
+    /// div().text("Hello, world!");
+    /// ```
+    ///
+    /// Dynamic text:
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let text = Mutable::new("Hello, world!");
+    /// # let d: Div =
+    /// div().text(Sig(text.signal()));
+    /// ```
+    fn text<'a, T>(self, child: impl RefSignalOrValue<'a, Item = T>) -> Self
+    where
+        T: 'a + AsRef<str> + Into<String>;
+
+    /// Add a child to the element.
+    ///
+    /// # Example
+    ///
+    /// Add static children:
+    ///
+    /// ```no_run
+    /// # use html::{div, p, Div};
+    /// # use silkenweb::prelude::*;
+    /// # let div: Div =
+    /// div().child(p().text("Hello,")).child(p().text("world!"));
+    /// ```
+    ///
+    /// Add a dynamic child:
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let text = Mutable::new("Hello, world!");
+    ///
+    /// # let d: Div =
+    /// div().child(Sig(text.signal().map(|text| div().text(text))));
+    /// ```
+    fn child(self, child: impl SignalOrValue<Item = impl ChildNode<D>>) -> Self {
+        self.optional_child(child.map(|child| Some(child)))
+    }
+
+    /// Add a child from a signal.
+    ///
+    /// The child is replaced each time the signal yields a new value. This is
+    /// shorthand for `self.child(Sig(child))`.
+    ///
+    /// On each change, the previous child is removed and dropped, which
+    /// cancels any signals or futures it owned. A new child is inserted even
+    /// if it's identical to the old one, so use [`dedupe`] on the source
+    /// signal to avoid rebuilding the child when its input hasn't changed.
+    ///
+    /// [`dedupe`]: futures_signals::signal::SignalExt::dedupe
+    ///
+    /// # Example
+    ///
+    /// Show different content for each tab:
+    ///
+    /// ```no_run
+    /// # use html::{div, p, Div};
+    /// # use silkenweb::{node::Node, prelude::*};
+    /// let first_tab = Mutable::new(true);
+    ///
+    /// # let d: Div =
+    /// div().child_signal(first_tab.signal().map(|first_tab| -> Node {
+    ///     if first_tab {
+    ///         p().text("First tab").into()
+    ///     } else {
+    ///         div().text("Second tab").into()
+    ///     }
+    /// }));
+    /// ```
+    fn child_signal(self, child: impl Signal<Item = impl ChildNode<D>> + 'static) -> Self {
+        self.child(Sig(child))
+    }
+
+    /// Add a child to the slot called `name`.
+    ///
+    /// This sets the [`slot`][HtmlElement::slot] attribute on `child` before
+    /// adding it, which is useful when filling in the named slots of a custom
+    /// element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{div, h2, p, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*};
+    /// let card: Div<Dry> = div()
+    ///     .child_in_slot("header", h2().text("Title"))
+    ///     .child(p().text("Content"));
+    /// assert_eq!(
+    ///     card.freeze().to_string(),
+    ///     r#"<div><h2 slot="header">Title</h2><p>Content</p></div>"#
+    /// );
+    /// ```
+    fn child_in_slot(self, name: &str, child: impl HtmlElement + ChildNode<D>) -> Self {
+        self.child(child.slot(name))
+    }
+
+    /// Add a child from a signal, with CSS transitions between children.
+    ///
+    /// When the signal yields a new child, the old child isn't removed
+    /// straight away. Instead, `leave_class` is added to it, and it's removed
+    /// when its `transitionend` event fires. At the same time, the new child
+    /// is added with `enter_class`, which is removed after it has been
+    /// rendered for a frame, so it transitions to its normal style. This gives
+    /// a crossfade, for example, when switching tabs or routes.
+    ///
+    /// The first child is added without a transition. If `transitionend`
+    /// doesn't fire, for example because `leave_class` doesn't trigger a
+    /// transition, the old child is removed once its computed
+    /// `transition-duration` and `transition-delay` have passed. Without a DOM
+    /// element, such as when rendering on the server, the new child is added
+    /// without `enter_class`, and the old child is removed straight away.
+    ///
+    /// # Example
+    ///
+    /// With CSS:
+    ///
+    /// ```css
+    /// .tab { transition: opacity 0.3s; }
+    /// .tab-enter, .tab-leave { opacity: 0; }
+    /// ```
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let tab = Mutable::new(0);
+    ///
+    /// # let d: Div =
+    /// div().child_signal_with_transition(
+    ///     tab.signal().map(|tab| div().class("tab").text(format!("Tab {tab}"))),
+    ///     "tab-enter",
+    ///     "tab-leave",
+    /// );
+    /// ```
+    fn child_signal_with_transition<E>(
+        self,
+        child: impl Signal<Item = E> + 'static,
+        enter_class: &'static str,
+        leave_class: &'static str,
+    ) -> Self
+    where
+        E: Element + ChildNode<D>,
+    {
+        let ids = MutableVec::new();
+        let nodes = Rc::new(RefCell::new(HashMap::new()));
+        let mut next_id: u64 = 0;
+        let mut current: Option<Mutable<TransitionPhase>> = None;
+
+        let update_children = child.for_each({
+            clone!(ids, nodes);
+
+            move |child| {
+                let id = next_id;
+                next_id += 1;
+
+                let handle: ElementHandle<E::Dom, web_sys::Element> =
+                    ElementHandle(child.handle().0, PhantomData);
+
+                // Without a DOM element, there's nothing to transition, so we don't add
+                // `enter_class`.
+                let initial_phase = if current.is_some() && handle.try_dom_element().is_some() {
+                    TransitionPhase::Entering
+                } else {
+                    TransitionPhase::Present
+                };
+                let phase = Mutable::new(initial_phase);
+
+                if let Some(previous) = current.replace(phase.clone()) {
+                    previous.set(TransitionPhase::Leaving);
+                }
+
+                if initial_phase == TransitionPhase::Entering {
+                    // Wait for a frame to be rendered with `enter_class`, otherwise there's nothing
+                    // to transition from.
+                    clone!(phase);
+                    task::on_animation_frame(move || {
+                        task::on_animation_frame(move || {
+                            let mut phase = phase.lock_mut();
+
+                            if *phase == TransitionPhase::Entering {
+                                *phase = TransitionPhase::Present;
+                            }
+                        })
+                    });
+                }
+
+                let remove_after_transition = {
+                    clone!(ids, phase);
+
+                    async move {
+                        phase.signal().wait_for(TransitionPhase::Leaving).await;
+
+                        // Without a DOM element, there's nothing to transition.
+                        if handle.try_dom_element().is_some() {
+                            // Wait for `leave_class` to be rendered, so its styles are applied.
+                            let (rendered_tx, rendered_rx) = oneshot::channel();
+                            task::on_animation_frame(move || {
+                                let _ = rendered_tx.send(());
+                            });
+                            let _ = rendered_rx.await;
+
+                            // `transitionend` won't fire if the transition is cancelled, or the
+                            // element isn't displayed, so we also remove the child once the
+                            // transition should have finished.
+                            if let Some(elem) = handle.try_dom_element() {
+                                time::sleep(transition_timeout(&elem)).await;
+                            }
+                        }
+
+                        remove_transition_child(&ids, id);
+                    }
+                };
+
+                let node = child
+                    .classes(Sig(phase.signal().map(move |phase| match phase {
+                        TransitionPhase::Entering => Some(enter_class),
+                        TransitionPhase::Present => None,
+                        TransitionPhase::Leaving => Some(leave_class),
+                    })))
+                    .on("transitionend", {
+                        clone!(ids);
+
+                        move |event| {
+                            let event: web_sys::Event = event.unchecked_into();
+
+                            // Ignore transitions on descendants, which bubble up to us.
+                            if phase.get() == TransitionPhase::Leaving
+                                && event.target() == event.current_target()
+                            {
+                                remove_transition_child(&ids, id);
+                            }
+                        }
+                    })
+                    .spawn_future(remove_after_transition);
+
+                nodes.borrow_mut().insert(id, node.into());
+                ids.lock_mut().push(id);
+
+                async {}
+            }
+        });
+
+        self.children_signal(
+            ids.signal_vec()
+                .filter_map(move |id| nodes.borrow_mut().remove(&id)),
+        )
+        .spawn_future(update_children)
+    }
+
+    /// Add an optional child to the element.
+    ///
+    /// The child will update when the signal changes to `Some(..)`, and will be
+    /// removed when the signal changes to `None`.
+    ///
+    /// # Example
+    ///
+    /// Add a static optional child:
+    ///
+    /// ```no_run
+    /// # use html::{div, p, Div};
+    /// # use silkenweb::prelude::*;
+    /// let text = Mutable::new("hello");
+    ///
+    /// # let div: Div =
+    /// div().optional_child(Some(p().text("Hello, world!")));
+    /// ```
+    ///
+    /// Add a dynamic optional child:
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let text = Mutable::new("hello");
+    ///
+    /// # let div: Div =
+    /// div().optional_child(Sig(text.signal().map(|text| {
+    ///     if text.is_empty() {
+    ///         None
+    ///     } else {
+    ///         Some(div().text(text))
+    ///     }
+    /// })));
+    /// ```
+    ///
+    /// Convert children to a [`Node`] to choose between different element
+    /// types:
+    ///
+    /// ```no_run
+    /// # use html::{div, p, Div};
+    /// # use silkenweb::{node::Node, prelude::*};
+    /// let count = Mutable::new(0);
+    ///
+    /// # let d: Div =
+    /// div().optional_child(Sig(count.signal().map(|count| -> Option<Node> {
+    ///     match count {
+    ///         0 => None,
+    ///         1 => Some(p().text("One").into()),
+    ///         _ => Some(div().text("Many").into()),
+    ///     }
+    /// })));
+    /// ```
+    fn optional_child(self, child: impl SignalOrValue<Item = Option<impl ChildNode<D>>>) -> Self;
+
+    /// Add children to the element.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, p, Div};
+    /// # use silkenweb::prelude::*;
+    /// # let div: Div =
+    /// div().children([p().text("Hello,"), p().text("world!")]);
+    /// ```
+    fn children<N>(self, children: impl IntoIterator<Item = N>) -> Self
+    where
+        N: Into<Node<D>>;
+
+    /// Add children from a [`SignalVec`] to the element.
+    ///
+    /// See [counter_list](https://github.com/silkenweb/silkenweb/tree/main/examples/counter-list/src/main.rs)
+    /// for an example
     fn children_signal<N>(self, children: impl SignalVec<Item = N> + 'static) -> Self
     where
         N: Into<Node<D>>;
+
+    /// Set the contents of the element to `html`, without escaping it.
+    ///
+    /// This is for HTML that's already been sanitized, such as the output
+    /// of a markdown renderer. Any scripts or event handler attributes in
+    /// `html` will run, so never pass it anything that could come from
+    /// an untrusted source.
+    ///
+    /// On [`Wet`] DOMs, this sets the `innerHTML` property, and on [`Dry`]
+    /// DOMs, `html` is written verbatim when the element is serialized. If
+    /// `html` is a signal, the contents are replaced wholesale each time it
+    /// changes.
+    ///
+    /// # Panics
+    ///
+    /// If the element already has children. Adding children after
+    /// `dangerous_inner_html`, with any of the methods on this trait, panics in
+    /// debug builds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*};
+    /// let d: Div<Dry> = div().dangerous_inner_html("<em>Hello</em>, world!");
+    ///
+    /// assert_eq!(d.freeze().to_string(), "<div><em>Hello</em>, world!</div>");
+    /// ```
+    fn dangerous_inner_html<'a>(
+        self,
+        html: impl RefSignalOrValue<'a, Item = impl AsRef<str> + 'a>,
+    ) -> Self;
+
+    /// Add children from a [`SignalVec`], reusing existing children by key.
+    ///
+    /// This is like [`Self::children_signal`], but each item in `children` is
+    /// identified by `key_fn`, and rendered with `render`. When `children`
+    /// replaces all its values, for example when it comes from
+    /// [`to_signal_vec`], any child with an existing key is kept and moved into
+    /// place, rather than rendered again. This preserves any state in the
+    /// existing child, and the fewest possible children are moved.
+    ///
+    /// Keys must be unique.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, input, Div};
+    /// # use silkenweb::prelude::*;
+    /// let names = Mutable::new(vec!["Alice", "Bob"]);
+    ///
+    /// # let d: Div =
+    /// div().children_signal_keyed(
+    ///     names.signal_cloned().to_signal_vec(),
+    ///     |name| *name,
+    ///     |name| input().value(name),
+    /// );
+    /// ```
+    ///
+    /// [`to_signal_vec`]: futures_signals::signal::SignalExt::to_signal_vec
+    fn children_signal_keyed<T, K, N>(
+        self,
+        children: impl SignalVec<Item = T> + 'static,
+        key_fn: impl FnMut(&T) -> K + 'static,
+        render: impl FnMut(T) -> N + 'static,
+    ) -> Self
+    where
+        K: Eq + Hash + Clone + 'static,
+        N: Into<Node<D>>,
+    {
+        self.children_signal(KeyedChildren::new(children, key_fn, |_| ()).map(render))
+    }
+
+    /// Add children from a [`SignalVec`], keyed by `key_fn`, with a signal of
+    /// each child's latest value.
+    ///
+    /// Like [`Self::children_signal_keyed`], each child is only rendered when
+    /// its key is first added. Rather than the value, `render` is given a
+    /// [`ReadOnlyMutable`] holding the latest value for the key. When
+    /// `children` yields a new value for an existing key, the mutable is
+    /// updated, so the child can update itself from the value's signal, without
+    /// being rendered again.
+    ///
+    /// When the children are reordered, the fewest possible children are
+    /// moved, and the rest stay where they are. Children keep their DOM nodes,
+    /// so state such as the text in an `<input>` is preserved. Moving an
+    /// element in the DOM removes focus from it in some browsers, so if the
+    /// focused child is one of those moved, it may lose focus.
+    ///
+    /// Keys must be unique.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, input, Div};
+    /// # use silkenweb::prelude::*;
+    /// #[derive(Clone)]
+    /// struct Todo {
+    ///     id: u64,
+    ///     text: String,
+    /// }
+    ///
+    /// let todos = MutableVec::new_with_values(vec![Todo {
+    ///     id: 0,
+    ///     text: "Write docs".to_string(),
+    /// }]);
+    ///
+    /// # let d: Div =
+    /// div().keyed_children(
+    ///     todos.signal_vec_cloned(),
+    ///     |todo| todo.id,
+    ///     |todo| input().value(Sig(todo.signal_ref(|todo| todo.text.clone()))),
+    /// );
+    /// ```
+    fn keyed_children<T, K, N>(
+        self,
+        children: impl SignalVec<Item = T> + 'static,
+        mut key_fn: impl FnMut(&T) -> K + 'static,
+        mut render: impl FnMut(ReadOnlyMutable<T>) -> N + 'static,
+    ) -> Self
+    where
+        T: 'static,
+        K: Eq + Hash + Clone + 'static,
+        N: Into<Node<D>>,
+    {
+        let values: Rc<RefCell<HashMap<K, Mutable<T>>>> = Rc::new(RefCell::new(HashMap::new()));
+
+        let children = children.map({
+            clone!(values);
+
+            move |value| {
+                let key = key_fn(&value);
+                let mut values = values.borrow_mut();
+
+                if let Some(existing) = values.get(&key) {
+                    existing.set(value);
+                    (key, existing.clone())
+                } else {
+                    let value = Mutable::new(value);
+                    values.insert(key.clone(), value.clone());
+                    (key, value)
+                }
+            }
+        });
+
+        self.children_signal(
+            KeyedChildren::new(
+                children,
+                |(key, _value)| key.clone(),
+                move |key| {
+                    values.borrow_mut().remove(&key);
+                },
+            )
+            .map(move |(_key, value)| render(value.read_only())),
+        )
+    }
 }
 
 /// An element that can be a shadow host.
@@ -846,6 +2459,180 @@ where
     }
 }
 
+impl<D> GenericElement<D, Const>
+where
+    D: Dom,
+{
+    /// Write the HTML for this element to `w`.
+    ///
+    /// The HTML is written as the element tree is traversed, without building
+    /// an intermediate `String`. The output is the same as [`ToString`].
+    ///
+    /// ```
+    /// # use html::{div, p};
+    /// # use silkenweb::{
+    /// #     dom::Dry,
+    /// #     node::element::{Const, GenericElement},
+    /// #     prelude::*,
+    /// # };
+    /// let app: GenericElement<Dry, Const> = div().id("app").child(p().text("Hello!")).into();
+    /// let mut html = String::new();
+    /// app.write_html(&mut html).unwrap();
+    /// assert_eq!(html, r#"<div id="app"><p>Hello!</p></div>"#);
+    /// ```
+    pub fn write_html(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        write!(w, "{self}")
+    }
+
+    /// Render any pending updates, then write the HTML for this element to
+    /// `w`.
+    ///
+    /// This awaits [`task::flush`] first, so any children or attributes that
+    /// depend on signals are up to date before they're written.
+    pub async fn render_html(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        task::flush().await;
+        self.write_html(w)
+    }
+
+    /// Render any pending updates, then return the HTML for this element.
+    ///
+    /// This awaits [`task::flush`] first, so the HTML doesn't depend on
+    /// whether signals have produced their values yet. See [`task::flush`] for
+    /// exactly what is awaited.
+    ///
+    /// ```
+    /// # use html::div;
+    /// # use silkenweb::{
+    /// #     dom::Dry,
+    /// #     node::element::{Const, GenericElement},
+    /// #     prelude::*,
+    /// #     task::server,
+    /// # };
+    /// # server::block_on(server::scope(async {
+    /// let text = Mutable::new("Hello!");
+    /// let app: GenericElement<Dry, Const> = div().text(Sig(text.signal())).into();
+    /// assert_eq!(app.render_to_string().await, "<div>Hello!</div>");
+    /// # }))
+    /// ```
+    pub async fn render_to_string(&self) -> String {
+        task::flush().await;
+        self.to_string()
+    }
+}
+
+impl GenericElement<Dry, Const> {
+    /// Render any pending updates, then return the HTML for this element and
+    /// for `<head>`.
+    ///
+    /// This is like [`render_to_string`][Self::render_to_string], but it also
+    /// returns [`Dry::head_inner_html`]. The body is serialized first, so any
+    /// stylesheets that components add with [`Document::mount_in_head`] while
+    /// they're rendered are included in the head. Elements are only mounted
+    /// in the head once for each `id`.
+    ///
+    /// ```
+    /// # use html::{div, style};
+    /// # use silkenweb::{
+    /// #     document::Document,
+    /// #     dom::Dry,
+    /// #     node::element::{Const, GenericElement},
+    /// #     prelude::*,
+    /// #     task::server,
+    /// # };
+    /// # server::block_on(server::scope(async {
+    /// let text = Mutable::new("Hello!");
+    /// let app: GenericElement<Dry, Const> = div()
+    ///     .text(Sig(text.signal().map(|text| {
+    ///         Dry::mount_in_head("my-style", style().text("div {}"));
+    ///         text
+    ///     })))
+    ///     .into();
+    /// let html = app.render_with_head().await;
+    ///
+    /// assert_eq!(html.body, "<div>Hello!</div>");
+    /// assert_eq!(html.head, r#"<style id="my-style">div {}</style>"#);
+    /// # }))
+    /// ```
+    pub async fn render_with_head(&self) -> RenderedHtml {
+        let body = self.render_to_string().await;
+        let head = Dry::head_inner_html();
+
+        RenderedHtml { head, body }
+    }
+
+    /// The HTML for this element, with each element on its own, indented,
+    /// line.
+    ///
+    /// This is for reading the HTML, for example when a snapshot test fails.
+    /// It doesn't preserve whitespace between elements, so use
+    /// [`ToString`] to serve the HTML. Elements that only contain text, and
+    /// elements where whitespace matters, such as `<pre>`, are kept on one
+    /// line.
+    ///
+    /// ```
+    /// # use html::{br, div, input, p};
+    /// # use silkenweb::{
+    /// #     dom::Dry,
+    /// #     node::element::{Const, GenericElement},
+    /// #     prelude::*,
+    /// # };
+    /// let app: GenericElement<Dry, Const> = div()
+    ///     .id("app")
+    ///     .child(p().text("Hello,"))
+    ///     .child(br())
+    ///     .child(input().disabled(true))
+    ///     .into();
+    ///
+    /// assert_eq!(
+    ///     app.to_pretty_string(),
+    ///     r#"<div id="app">
+    ///   <p>Hello,</p>
+    ///   <br>
+    ///   <input disabled="">
+    /// </div>
+    /// "#
+    /// );
+    /// ```
+    pub fn to_pretty_string(&self) -> String {
+        self.to_pretty_string_with(&PrettyOptions::default())
+    }
+
+    /// Like [`Self::to_pretty_string`], but with `options` to control the
+    /// format.
+    pub fn to_pretty_string_with(&self, options: &PrettyOptions) -> String {
+        let mut html = String::new();
+        self.element.write_pretty(&mut html, options, 0).unwrap();
+        html
+    }
+}
+
+/// Options for [`GenericElement::to_pretty_string_with`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrettyOptions {
+    /// The number of spaces to indent each level by.
+    pub indent: usize,
+    /// Sort attributes by name, so the order they were added in doesn't
+    /// change the output.
+    pub sort_attributes: bool,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            sort_attributes: false,
+        }
+    }
+}
+
+/// The HTML from [`GenericElement::render_with_head`].
+pub struct RenderedHtml {
+    /// The inner HTML for `<head>`.
+    pub head: String,
+    /// The HTML for the element.
+    pub body: String,
+}
+
 impl<Param, D> GenericElement<Template<Param, D>, Const>
 where
     D: InstantiableDom,
@@ -859,7 +2646,7 @@ where
     }
 }
 
-fn spawn_cancelable_future(
+pub(crate) fn spawn_cancelable_future(
     future: impl Future<Output = ()> + 'static,
 ) -> DiscardOnDrop<CancelableFutureHandle> {
     let (handle, cancelable_future) = cancelable_future(future, || ());
@@ -899,6 +2686,388 @@ impl<D: Dom, DomElement: JsCast + Clone> ElementHandle<D, DomElement> {
         self.try_dom_element()
             .expect("Dom type doesn't support element handles")
     }
+
+    /// A signal of the associated DOM element.
+    ///
+    /// This is [`None`] until there's a DOM element, then `Some(element)`.
+    /// [`Wet`] elements always have a DOM element, so the first value is
+    /// `Some`. [`Hydro`] elements get a DOM element when they're hydrated,
+    /// and [`Dry`] elements never do.
+    ///
+    /// When the element is hydrated, the DOM element is set from an effect, so
+    /// it's rendered by the time the signal yields it. That makes this useful
+    /// for measuring an element once. DOM elements can't be shared between
+    /// threads, so neither can the signal.
+    ///
+    /// [`Dry`]: crate::dom::Dry
+    pub fn dom_element_signal(&self) -> impl Signal<Item = Option<DomElement>> + 'static
+    where
+        DomElement: 'static,
+    {
+        let dom_element = Mutable::new(self.try_dom_element());
+
+        if dom_element.lock_ref().is_none() {
+            let set_dom_element = dom_element.clone();
+            self.0
+                .clone()
+                .effect(move |elem| set_dom_element.set(Some(elem.clone().dyn_into().unwrap())));
+        }
+
+        dom_element.signal_cloned()
+    }
+
+    /// A signal of whether the element is in the viewport.
+    ///
+    /// This is [`Self::visible_signal_with`] the default
+    /// [`VisibilityOptions`], so it's `true` when any of the element is
+    /// visible.
+    pub fn visible_signal(&self) -> impl Signal<Item = bool> + 'static {
+        self.visible_signal_with(VisibilityOptions::default())
+    }
+
+    /// A signal of whether the element is in the viewport, using `options`.
+    ///
+    /// Visibility is observed with an [`IntersectionObserver`], which is
+    /// created once the element is rendered, and disconnected when the signal
+    /// is dropped. This is useful for lazy loading content or infinite
+    /// scrolling.
+    ///
+    /// The signal is `false` until the element is rendered. For [`Dry`]
+    /// elements, or [`Hydro`] elements that aren't hydrated, it's always
+    /// `false`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let placeholder: Div = div();
+    /// let is_visible = placeholder.handle().visible_signal();
+    /// let app: Div = div()
+    ///     .child(placeholder)
+    ///     .text(Sig(is_visible.map(|visible| if visible { "Loading" } else { "" })));
+    /// ```
+    ///
+    /// [`IntersectionObserver`]: https://developer.mozilla.org/en-US/docs/Web/API/IntersectionObserver
+    pub fn visible_signal_with(
+        &self,
+        options: VisibilityOptions,
+    ) -> impl Signal<Item = bool> + 'static {
+        let is_visible = Mutable::new(false);
+        let observer = Rc::new(RefCell::new(None));
+
+        self.0.clone().effect({
+            clone!(is_visible, observer);
+
+            move |elem| {
+                observer.replace(Some(IntersectionObserver::new(
+                    elem,
+                    &options,
+                    move |visible| is_visible.set_neq(visible),
+                )));
+            }
+        });
+
+        is_visible.signal().map(move |visible| {
+            // Keep the observer alive until the signal is dropped.
+            let _observer = &observer;
+            visible
+        })
+    }
+
+    /// A signal of the width and height of the element's content box.
+    ///
+    /// The size is observed with a [`ResizeObserver`], which is created once
+    /// the element is rendered, and disconnected when the signal is dropped.
+    /// See [`Element::on_resize`] to call a function instead.
+    ///
+    /// The signal is `(0.0, 0.0)` until the element is rendered. For [`Dry`]
+    /// elements, or [`Hydro`] elements that aren't hydrated, it's always
+    /// `(0.0, 0.0)`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let panel: Div = div();
+    /// let is_narrow = panel
+    ///     .handle()
+    ///     .size_signal()
+    ///     .map(|(width, _height)| width < 400.0);
+    /// let app: Div = div()
+    ///     .child(panel)
+    ///     .classes(Sig(is_narrow.map(|narrow| narrow.then_some("narrow"))));
+    /// ```
+    ///
+    /// [`ResizeObserver`]: https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver
+    pub fn size_signal(&self) -> impl Signal<Item = (f64, f64)> + 'static {
+        ResizeObserver::size_signal::<D>(&self.0).map(|size| size.unwrap_or((0.0, 0.0)))
+    }
+
+    /// A signal that ticks once changes to the element's descendants have
+    /// settled.
+    ///
+    /// Changes to the children, or the text, of any descendant are observed
+    /// with a [`MutationObserver`]. The signal yields `()` after there have
+    /// been no changes for `duration`, so a batch of changes from another
+    /// library results in a single tick. It doesn't yield until there's been a
+    /// change. The observer is created once the element is rendered, and
+    /// disconnected when the signal is dropped. For [`Dry`] elements, or
+    /// [`Hydro`] elements that aren't hydrated, the signal never yields.
+    ///
+    /// See [`time::debounce`] for how changes are debounced.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use html::{div, Div};
+    /// # use silkenweb::prelude::*;
+    /// let chart: Div = div();
+    /// let chart_handle = chart.handle();
+    /// let child_count = Mutable::new(0);
+    /// let chart = chart.spawn_future(
+    ///     chart_handle
+    ///         .observe_children_debounced(Duration::from_millis(100))
+    ///         .for_each(move |()| {
+    ///             child_count.set(chart_handle.dom_element().child_element_count());
+    ///             async {}
+    ///         }),
+    /// );
+    /// ```
+    ///
+    /// [`MutationObserver`]: https://developer.mozilla.org/en-US/docs/Web/API/MutationObserver
+    pub fn observe_children_debounced(
+        &self,
+        duration: Duration,
+    ) -> impl Signal<Item = ()> + 'static {
+        let (changes_tx, changes_rx) = mpsc::unbounded();
+        let observer = Rc::new(RefCell::new(None));
+
+        self.0.clone().effect({
+            clone!(observer);
+
+            move |elem| {
+                observer.replace(Some(MutationObserver::children(elem, move || {
+                    let _ = changes_tx.unbounded_send(());
+                })));
+            }
+        });
+
+        time::debounce(Changes(changes_rx), duration).map(move |()| {
+            // Keep the observer alive until the signal is dropped.
+            let _observer = &observer;
+        })
+    }
+
+    /// Dispatch `event` to the element.
+    ///
+    /// This goes through the DOM, so the event propagates like any other, and
+    /// handlers added with [`Element::on`] are called. It's mostly useful for
+    /// testing. The result is `false` if the event was cancelable and a
+    /// handler called `preventDefault`.
+    ///
+    /// This does nothing, and returns `Ok(true)`, for non-[`Wet`] elements.
+    pub fn dispatch_event(&self, event: &web_sys::Event) -> Result<bool, JsValue> {
+        self.0
+            .try_dom_element()
+            .map_or(Ok(true), |elem| elem.dispatch_event(event))
+    }
+
+    /// Dispatch a `click` event to the element.
+    ///
+    /// The event bubbles and is cancelable, like a real click. See
+    /// [`Self::dispatch_event`].
+    pub fn dispatch_click(&self) -> Result<bool, JsValue> {
+        self.dispatch_new_event(|_| {
+            web_sys::MouseEvent::new_with_mouse_event_init_dict(
+                "click",
+                web_sys::MouseEventInit::new()
+                    .bubbles(true)
+                    .cancelable(true),
+            )
+            .map(Into::into)
+        })
+    }
+
+    /// Set the element's `value` property, then dispatch an `input` event.
+    ///
+    /// This is what happens when a user edits an `<input>`, `<textarea>` or
+    /// `<select>`, so handlers see the new value. Setting the value before
+    /// dispatching the event is what makes this useful for testing two way
+    /// bindings. See [`Self::dispatch_event`].
+    pub fn dispatch_input(&self, value: &str) -> Result<bool, JsValue> {
+        self.dispatch_new_event(|elem| {
+            js_sys::Reflect::set(elem, &"value".into(), &value.into())?;
+
+            web_sys::InputEvent::new_with_event_init_dict(
+                "input",
+                web_sys::InputEventInit::new().bubbles(true),
+            )
+            .map(Into::into)
+        })
+    }
+
+    /// Dispatch a `keydown` event for `key` to the element.
+    ///
+    /// `key` is the [key value], for example `"Enter"` or `"a"`. The event
+    /// bubbles and is cancelable. See [`Self::dispatch_event`].
+    ///
+    /// [key value]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+    pub fn dispatch_keydown(&self, key: &str) -> Result<bool, JsValue> {
+        self.dispatch_new_event(|_| {
+            web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+                "keydown",
+                web_sys::KeyboardEventInit::new()
+                    .bubbles(true)
+                    .cancelable(true)
+                    .key(key),
+            )
+            .map(Into::into)
+        })
+    }
+
+    // Events can only be constructed in the browser, so we check there's a DOM
+    // element first.
+    fn dispatch_new_event(
+        &self,
+        new_event: impl FnOnce(&web_sys::Element) -> Result<web_sys::Event, JsValue>,
+    ) -> Result<bool, JsValue> {
+        self.0.try_dom_element().map_or(Ok(true), |elem| {
+            let event = new_event(&elem)?;
+            elem.dispatch_event(&event)
+        })
+    }
+}
+
+impl<D: Dom, DomElement> ElementHandle<D, DomElement>
+where
+    DomElement: JsCast + Clone + AsRef<web_sys::HtmlElement>,
+{
+    /// Focus the element.
+    ///
+    /// This does nothing if the element isn't [`Wet`] or a hydrated [`Hydro`]
+    /// element, so it can be used in code that's also rendered on the server.
+    pub fn focus(&self) -> Result<(), JsValue> {
+        self.with_html_element(|elem| elem.focus())
+    }
+
+    /// Focus the element without scrolling it into view.
+    ///
+    /// This is [`Self::focus_with_options`], with
+    /// [`FocusOptions::prevent_scroll`] set.
+    pub fn focus_without_scroll(&self) -> Result<(), JsValue> {
+        self.focus_with_options(FocusOptions {
+            prevent_scroll: true,
+            ..FocusOptions::default()
+        })
+    }
+
+    /// Focus the element with `options`.
+    ///
+    /// See [`focus`] on MDN for details of each option. Like [`Self::focus`],
+    /// it does nothing for non-[`Wet`] elements.
+    ///
+    /// [`focus`]: https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/focus
+    pub fn focus_with_options(&self, options: FocusOptions) -> Result<(), JsValue> {
+        self.with_html_element(|elem| {
+            let js_options = js_sys::Object::new();
+            js_sys::Reflect::set(
+                &js_options,
+                &"preventScroll".into(),
+                &JsValue::from_bool(options.prevent_scroll),
+            )?;
+
+            if let Some(focus_visible) = options.focus_visible {
+                js_sys::Reflect::set(
+                    &js_options,
+                    &"focusVisible".into(),
+                    &JsValue::from_bool(focus_visible),
+                )?;
+            }
+
+            let focus: js_sys::Function =
+                js_sys::Reflect::get(elem, &"focus".into())?.dyn_into()?;
+            focus.call1(elem, &js_options)?;
+            Ok(())
+        })
+    }
+
+    /// Remove focus from the element.
+    ///
+    /// This does nothing for non-[`Wet`] elements.
+    pub fn blur(&self) -> Result<(), JsValue> {
+        self.with_html_element(|elem| elem.blur())
+    }
+
+    /// Scroll the element into view.
+    ///
+    /// This does nothing for non-[`Wet`] elements.
+    pub fn scroll_into_view(&self) {
+        if let Some(elem) = self.try_dom_element() {
+            let elem: &web_sys::HtmlElement = elem.as_ref();
+            elem.scroll_into_view();
+        }
+    }
+
+    /// Show the element, if it's a [popover].
+    ///
+    /// In browsers that don't support popovers, the `hidden` attribute is
+    /// removed instead. This does nothing for non-[`Wet`] elements.
+    ///
+    /// [popover]: crate::elements::HtmlElement::popover
+    pub fn show_popover(&self) -> Result<(), JsValue> {
+        self.call_popover_method("showPopover", |elem| elem.set_hidden(false))
+    }
+
+    /// Hide the element, if it's a [popover].
+    ///
+    /// In browsers that don't support popovers, the `hidden` attribute is set
+    /// instead. This does nothing for non-[`Wet`] elements.
+    ///
+    /// [popover]: crate::elements::HtmlElement::popover
+    pub fn hide_popover(&self) -> Result<(), JsValue> {
+        self.call_popover_method("hidePopover", |elem| elem.set_hidden(true))
+    }
+
+    /// Show the element if it's a hidden [popover], otherwise hide it.
+    ///
+    /// In browsers that don't support popovers, the `hidden` attribute is
+    /// toggled instead. This does nothing for non-[`Wet`] elements.
+    ///
+    /// [popover]: crate::elements::HtmlElement::popover
+    pub fn toggle_popover(&self) -> Result<(), JsValue> {
+        self.call_popover_method("togglePopover", |elem| elem.set_hidden(!elem.hidden()))
+    }
+
+    fn call_popover_method(
+        &self,
+        name: &str,
+        fallback: impl FnOnce(&web_sys::HtmlElement),
+    ) -> Result<(), JsValue> {
+        self.with_html_element(|elem| {
+            // `web-sys` doesn't have bindings for the popover API yet, and we need
+            // to check it's supported anyway.
+            let method = js_sys::Reflect::get(elem, &intern_str(name).into())?;
+
+            if let Some(method) = method.dyn_ref::<js_sys::Function>() {
+                method.call0(elem)?;
+            } else {
+                fallback(elem);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn with_html_element(
+        &self,
+        f: impl FnOnce(&web_sys::HtmlElement) -> Result<(), JsValue>,
+    ) -> Result<(), JsValue> {
+        self.try_dom_element()
+            .map_or(Ok(()), |elem| f(elem.as_ref()))
+    }
 }
 
 impl<D: Dom> ElementHandle<D, web_sys::Element> {
@@ -911,7 +3080,9 @@ impl<D: Dom> ElementHandle<D, web_sys::Element> {
 }
 
 /// The namespace of a DOM element.
-#[derive(Copy, Clone, Eq, PartialEq)]
+///
+/// See [`GenericElement::namespace`] to get the namespace of an element.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Namespace {
     /// New elements in the `Html` namespace are created with `create_element`,
     /// thus avoiding converting the namespace to a javascript string.
@@ -0,0 +1,354 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    iter,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_signals::signal_vec::{SignalVec, VecDiff};
+use pin_project::pin_project;
+
+/// Translate `VecDiff::Replace` into moves, inserts and removals.
+///
+/// Children are identified by key, and any children that are already present
+/// are kept, so their DOM nodes are reused. The new value for an existing key
+/// is discarded. `on_remove` is called with the key of each child that's
+/// removed.
+#[pin_project]
+pub struct KeyedChildren<S: SignalVec, F, K, R> {
+    #[pin]
+    children: S,
+    key_fn: F,
+    on_remove: R,
+    keys: Vec<K>,
+    pending: VecDeque<VecDiff<S::Item>>,
+}
+
+impl<S, F, K, R> KeyedChildren<S, F, K, R>
+where
+    S: SignalVec,
+    F: FnMut(&S::Item) -> K,
+    K: Eq + Hash + Clone,
+    R: FnMut(K),
+{
+    pub fn new(children: S, key_fn: F, on_remove: R) -> Self {
+        Self {
+            children,
+            key_fn,
+            on_remove,
+            keys: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<S, F, K, R> SignalVec for KeyedChildren<S, F, K, R>
+where
+    S: SignalVec,
+    F: FnMut(&S::Item) -> K,
+    K: Eq + Hash + Clone,
+    R: FnMut(K),
+{
+    type Item = S::Item;
+
+    fn poll_vec_change(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<VecDiff<Self::Item>>> {
+        let mut proj = self.project();
+
+        loop {
+            if let Some(diff) = proj.pending.pop_front() {
+                return Poll::Ready(Some(diff));
+            }
+
+            match proj.children.as_mut().poll_vec_change(cx) {
+                Poll::Ready(Some(diff)) => {
+                    let mut keyed = Keyed {
+                        key_fn: &mut *proj.key_fn,
+                        on_remove: &mut *proj.on_remove,
+                        keys: &mut *proj.keys,
+                        pending: &mut *proj.pending,
+                    };
+                    keyed.apply(diff);
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+struct Keyed<'a, T, F, K, R> {
+    key_fn: &'a mut F,
+    on_remove: &'a mut R,
+    keys: &'a mut Vec<K>,
+    pending: &'a mut VecDeque<VecDiff<T>>,
+}
+
+impl<'a, T, F, K, R> Keyed<'a, T, F, K, R>
+where
+    F: FnMut(&T) -> K,
+    K: Eq + Hash + Clone,
+    R: FnMut(K),
+{
+    fn apply(&mut self, diff: VecDiff<T>) {
+        match diff {
+            VecDiff::Replace { values } => {
+                if self.keys.is_empty() {
+                    *self.keys = values.iter().map(&mut *self.key_fn).collect();
+                    self.pending.push_back(VecDiff::Replace { values });
+                } else {
+                    self.replace(values);
+                }
+            }
+            VecDiff::InsertAt { index, value } => {
+                self.keys.insert(index, (self.key_fn)(&value));
+                self.pending.push_back(VecDiff::InsertAt { index, value });
+            }
+            VecDiff::UpdateAt { index, value } => {
+                let key = (self.key_fn)(&value);
+
+                if self.keys[index] != key {
+                    let old_key = std::mem::replace(&mut self.keys[index], key);
+                    (self.on_remove)(old_key);
+                    self.pending.push_back(VecDiff::UpdateAt { index, value });
+                }
+            }
+            VecDiff::RemoveAt { index } => {
+                (self.on_remove)(self.keys.remove(index));
+                self.pending.push_back(VecDiff::RemoveAt { index });
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+            } => {
+                let key = self.keys.remove(old_index);
+                self.keys.insert(new_index, key);
+                self.pending.push_back(VecDiff::Move {
+                    old_index,
+                    new_index,
+                });
+            }
+            VecDiff::Push { value } => {
+                self.keys.push((self.key_fn)(&value));
+                self.pending.push_back(VecDiff::Push { value });
+            }
+            VecDiff::Pop {} => {
+                if let Some(key) = self.keys.pop() {
+                    (self.on_remove)(key);
+                }
+
+                self.pending.push_back(VecDiff::Pop {});
+            }
+            VecDiff::Clear {} => {
+                for key in self.keys.drain(..) {
+                    (self.on_remove)(key);
+                }
+
+                self.pending.push_back(VecDiff::Clear {});
+            }
+        }
+    }
+
+    fn replace(&mut self, values: Vec<T>) {
+        let new_keys: Vec<K> = values.iter().map(&mut *self.key_fn).collect();
+        let new_key_set: HashSet<&K> = new_keys.iter().collect();
+        debug_assert_eq!(new_key_set.len(), new_keys.len(), "Keys must be unique");
+
+        let mut kept_keys = Vec::with_capacity(self.keys.len());
+
+        for (index, key) in self.keys.drain(..).enumerate().rev() {
+            if new_key_set.contains(&key) {
+                kept_keys.push(key);
+            } else {
+                (self.on_remove)(key);
+                self.pending.push_back(VecDiff::RemoveAt { index });
+            }
+        }
+
+        kept_keys.reverse();
+
+        let old_positions: HashMap<&K, usize> = kept_keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+        let old_indices: Vec<Option<usize>> = new_keys
+            .iter()
+            .map(|key| old_positions.get(key).copied())
+            .collect();
+        let stays = longest_increasing_subsequence(&old_indices);
+        let mut values: Vec<Option<T>> = values.into_iter().map(Some).collect();
+        let mut positions = Positions::new(kept_keys.len());
+
+        // Working backwards, put each child that isn't in the longest increasing
+        // subsequence directly before its successor. This moves the fewest
+        // existing children.
+        let mut successor_group = positions.end_group();
+
+        for index in (0..new_keys.len()).rev() {
+            if let Some(old_index) = old_indices[index].filter(|_| stays[index]) {
+                successor_group = old_index;
+                continue;
+            }
+
+            let mut target = positions.group_start(successor_group);
+
+            if let Some(old_index) = old_indices[index] {
+                let current = positions.remove_unmoved(old_index);
+
+                if current < target {
+                    target -= 1;
+                }
+
+                if current != target {
+                    self.pending.push_back(VecDiff::Move {
+                        old_index: current,
+                        new_index: target,
+                    });
+                }
+            } else {
+                self.pending.push_back(VecDiff::InsertAt {
+                    index: target,
+                    value: values[index].take().unwrap(),
+                });
+            }
+
+            positions.insert_before(successor_group);
+        }
+
+        *self.keys = new_keys;
+    }
+}
+
+/// Track the positions of children while they're reordered.
+///
+/// Children that haven't moved are in a group of their own, keyed by their
+/// index before the reorder. Each child that's moved goes directly before its
+/// successor, which is either the first child in a group, or the end. Moved
+/// children join their successor's group, in front of the children already
+/// there, so we only need to count the children in each group to find
+/// positions. The counts are kept in a Fenwick tree, so each lookup or update
+/// takes `O(log n)` time.
+///
+/// Each group has 2 slots: one for the children that have moved in front of
+/// it, then one for the child that was originally there. The last group is
+/// the end of the list.
+struct Positions(Vec<usize>);
+
+impl Positions {
+    fn new(len: usize) -> Self {
+        let mut positions = Self(vec![0; 2 * len + 1]);
+
+        for group in 0..len {
+            positions.increment(Self::unmoved_slot(group));
+        }
+
+        positions
+    }
+
+    fn end_group(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    /// The index of the first child in `group`.
+    fn group_start(&self, group: usize) -> usize {
+        self.prefix_sum(Self::moved_slot(group))
+    }
+
+    /// Remove an unmoved child from `group`, returning its index.
+    fn remove_unmoved(&mut self, group: usize) -> usize {
+        let slot = Self::unmoved_slot(group);
+        let index = self.prefix_sum(slot);
+        self.decrement(slot);
+        index
+    }
+
+    /// Insert a child at the front of `group`.
+    fn insert_before(&mut self, group: usize) {
+        self.increment(Self::moved_slot(group));
+    }
+
+    fn moved_slot(group: usize) -> usize {
+        2 * group
+    }
+
+    fn unmoved_slot(group: usize) -> usize {
+        2 * group + 1
+    }
+
+    /// The sum of all slots before `end`.
+    fn prefix_sum(&self, end: usize) -> usize {
+        let mut sum = 0;
+        let mut index = end;
+
+        while index > 0 {
+            sum += self.0[index - 1];
+            index &= index - 1;
+        }
+
+        sum
+    }
+
+    fn increment(&mut self, slot: usize) {
+        for index in self.covering(slot) {
+            self.0[index] += 1;
+        }
+    }
+
+    fn decrement(&mut self, slot: usize) {
+        for index in self.covering(slot) {
+            self.0[index] -= 1;
+        }
+    }
+
+    /// The indices of the tree nodes that include `slot`.
+    fn covering(&self, slot: usize) -> impl Iterator<Item = usize> {
+        let len = self.0.len();
+
+        iter::successors(Some(slot + 1), |index| {
+            Some(index + (index & index.wrapping_neg()))
+        })
+        .take_while(move |&index| index <= len)
+        .map(|index| index - 1)
+    }
+}
+
+/// Find a longest increasing subsequence, ignoring `None`s.
+///
+/// The result has an entry for each item in `sequence`, which is `true` if the
+/// item is in the subsequence.
+fn longest_increasing_subsequence(sequence: &[Option<usize>]) -> Vec<bool> {
+    // `tails[len]` is the index into `sequence` of the smallest tail of an
+    // increasing subsequence of length `len + 1`.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut predecessors = vec![None; sequence.len()];
+
+    for (index, value) in sequence.iter().enumerate() {
+        if let Some(value) = value {
+            let len = tails.partition_point(|&tail| sequence[tail] < Some(*value));
+
+            if len > 0 {
+                predecessors[index] = Some(tails[len - 1]);
+            }
+
+            if len == tails.len() {
+                tails.push(index);
+            } else {
+                tails[len] = index;
+            }
+        }
+    }
+
+    let mut in_subsequence = vec![false; sequence.len()];
+    let mut next = tails.last().copied();
+
+    while let Some(index) = next {
+        in_subsequence[index] = true;
+        next = predecessors[index];
+    }
+
+    in_subsequence
+}
@@ -0,0 +1,117 @@
+use std::vec;
+
+use super::{ChildNode, Node};
+use crate::dom::{DefaultDom, Dom};
+
+/// A list of sibling nodes, without a wrapper element.
+///
+/// Components can return a [`Fragment`] when they need to produce several top
+/// level nodes, for example table rows or list items, where a wrapper element
+/// isn't allowed. Add the nodes to a parent with
+/// [`ParentElement::children`], and they're inserted as ordinary siblings, in
+/// order.
+///
+/// To change a fragment dynamically, convert a signal of fragments into a
+/// [`SignalVec`] with [`to_signal_vec`], and add it with
+/// [`ParentElement::children_signal`]. Each time the signal changes, all the
+/// nodes from the previous fragment are removed together and replaced with
+/// the new ones.
+///
+/// Hydration doesn't need to know about fragments. By the time the parent is
+/// hydrated, the fragment's nodes are just children of the parent, so they're
+/// reconciled against the existing DOM siblings one by one, like any other
+/// children.
+///
+/// # Example
+///
+/// ```no_run
+/// # use html::{table, td, tr, Table};
+/// # use silkenweb::{node::Fragment, prelude::*};
+/// fn rows() -> Fragment {
+///     Fragment::new()
+///         .child(tr().child(td().text("Row 1")))
+///         .child(tr().child(td().text("Row 2")))
+/// }
+///
+/// # let t: Table =
+/// table().children(rows());
+/// ```
+///
+/// A dynamic fragment:
+///
+/// ```no_run
+/// # use html::{li, ul, Ul};
+/// # use silkenweb::{node::Fragment, prelude::*};
+/// fn items(count: usize) -> Fragment {
+///     (0..count).map(|i| li().text(format!("Item {i}"))).collect()
+/// }
+///
+/// let count = Mutable::new(1);
+///
+/// # let list: Ul =
+/// ul().children_signal(
+///     count
+///         .signal()
+///         .map(|count| items(count).into_iter().collect::<Vec<_>>())
+///         .to_signal_vec(),
+/// );
+/// ```
+///
+/// [`ParentElement::children`]: super::element::ParentElement::children
+/// [`ParentElement::children_signal`]: super::element::ParentElement::children_signal
+/// [`SignalVec`]: futures_signals::signal_vec::SignalVec
+/// [`to_signal_vec`]: futures_signals::signal::SignalExt::to_signal_vec
+pub struct Fragment<D: Dom = DefaultDom>(Vec<Node<D>>);
+
+impl<D: Dom> Fragment<D> {
+    /// Construct an empty fragment.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Add `child` to the end of the fragment.
+    pub fn child(mut self, child: impl ChildNode<D>) -> Self {
+        self.0.push(child.into());
+        self
+    }
+
+    /// Add `children` to the end of the fragment.
+    pub fn children<N>(mut self, children: impl IntoIterator<Item = N>) -> Self
+    where
+        N: ChildNode<D>,
+    {
+        self.0.extend(children.into_iter().map(N::into));
+        self
+    }
+
+    /// The number of nodes in the fragment.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is the fragment empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<D: Dom> Default for Fragment<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Dom, N: ChildNode<D>> FromIterator<N> for Fragment<D> {
+    fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
+        Self::new().children(iter)
+    }
+}
+
+impl<D: Dom> IntoIterator for Fragment<D> {
+    type IntoIter = vec::IntoIter<Node<D>>;
+    type Item = Node<D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
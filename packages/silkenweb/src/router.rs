@@ -1,7 +1,8 @@
 //! URL based routing.
 //!
 //! Get the URL path with [`url_path`], and set it with [`set_url_path`] or a
-//! link to a fragment like `<a href="#anchor" ...>`.
+//! link to a fragment like `<a href="#anchor" ...>`. Use a [`Route`] to match
+//! the path against a pattern, and extract parameters from it.
 //!
 //! # Example
 //!
@@ -27,6 +28,7 @@
 use std::{collections::HashMap, fmt::Display};
 
 use futures_signals::signal::{Mutable, ReadOnlyMutable};
+use silkenweb_base::window;
 use silkenweb_macros::cfg_browser;
 
 use crate::{
@@ -36,6 +38,10 @@ use crate::{
     task,
 };
 
+mod route;
+
+pub use route::{Route, RouteMatch};
+
 /// Represent the path portion of a URL (including any query string)
 #[derive(Clone, Eq, PartialEq)]
 pub struct UrlPath {
@@ -216,10 +222,27 @@ pub fn set_url_path(path: impl Into<UrlPath>) {
     arch::set_url_path(path)
 }
 
+/// Replace the path portion of the URL.
+///
+/// This is like [`set_url_path`], but it replaces the current history entry
+/// instead of pushing a new one, so the back button won't return to the
+/// current path. This is useful for redirects.
+pub fn replace_url_path(path: impl Into<UrlPath>) {
+    arch::replace_url_path(path)
+}
+
+/// Go back to the previous history entry.
+///
+/// The [`url_path()`] signal will be updated once the browser has navigated.
+/// This does nothing on the server, as there's no history.
+pub fn back() {
+    arch::back()
+}
+
 /// Set up an HTML `<a>` element for routing.
 ///
 /// Return an `<a>` element builder with the `href` attribute set to `path` and
-/// an `on_click` handler. Modifier keys are correctly handled.
+/// an `on_click` handler. See [`link_clicked`] for which clicks are routed.
 ///
 /// # Example
 ///
@@ -228,6 +251,18 @@ pub fn set_url_path(path: impl Into<UrlPath>) {
 /// use silkenweb::{prelude::*, router::anchor};
 /// let link: A = anchor("/my-path").text("click me");
 /// ```
+///
+/// Use [`Route::path`] to link to a route:
+///
+/// ```no_run
+/// # use html::{a, A};
+/// use silkenweb::{
+///     prelude::*,
+///     router::{anchor, Route},
+/// };
+/// let edit_todo = Route::parse("todos/:id/edit");
+/// let link: A = anchor(edit_todo.path([("id", "42")]).unwrap()).text("Edit");
+/// ```
 pub fn anchor<D: Dom>(path: impl Into<String>) -> A<D> {
     let path = path.into();
 
@@ -236,7 +271,16 @@ pub fn anchor<D: Dom>(path: impl Into<String>) -> A<D> {
 
 /// An `on_click` handler for routed `<a>` elements.
 ///
-/// This will correctly deal with modifier keys. See also: [`anchor`].
+/// The click is handled with [`set_url_path`] unless it should be left to the
+/// browser, which is when:
+///
+/// - A modifier key is pressed, or it's not the main mouse button, so the user
+///   can open the link in a new tab or window.
+/// - The element has a `target` other than `_self`, such as `_blank`.
+/// - The link goes to another origin.
+/// - Another handler has already called `prevent_default`.
+///
+/// See also: [`anchor`].
 ///
 /// # Example
 ///
@@ -250,16 +294,31 @@ pub fn link_clicked(
     path: impl Into<String>,
 ) -> impl FnMut(web_sys::MouseEvent, web_sys::HtmlAnchorElement) + 'static {
     let path = path.into();
-    move |ev, _| {
+    move |ev, elem| {
         let modifier_key_pressed = ev.meta_key() || ev.ctrl_key() || ev.shift_key() || ev.alt_key();
-
-        if !modifier_key_pressed {
-            ev.prevent_default();
-            set_url_path(path.as_str());
+        let target = elem.target();
+        let new_browsing_context = !(target.is_empty() || target == "_self");
+
+        if modifier_key_pressed
+            || ev.button() != 0
+            || ev.default_prevented()
+            || new_browsing_context
+            || !is_same_origin(&elem)
+        {
+            return;
         }
+
+        ev.prevent_default();
+        set_url_path(path.as_str());
     }
 }
 
+fn is_same_origin(elem: &web_sys::HtmlAnchorElement) -> bool {
+    window::location()
+        .origin()
+        .map_or(false, |origin| origin == elem.origin())
+}
+
 pub(crate) struct TaskLocal(Mutable<UrlPath>);
 
 impl Default for TaskLocal {
@@ -280,6 +339,12 @@ mod arch {
     pub fn set_url_path(path: impl Into<UrlPath>) {
         task::local::with(move |local| local.router.0.set(path.into()));
     }
+
+    pub fn replace_url_path(path: impl Into<UrlPath>) {
+        set_url_path(path)
+    }
+
+    pub fn back() {}
 }
 
 #[cfg_browser(true)]
@@ -298,14 +363,31 @@ mod arch {
     }
 
     pub fn set_url_path(path: impl Into<UrlPath>) {
+        update_url_path(path, |history, url| {
+            history.push_state_with_url(&JsValue::null(), "", Some(url))
+        })
+    }
+
+    pub fn replace_url_path(path: impl Into<UrlPath>) {
+        update_url_path(path, |history, url| {
+            history.replace_state_with_url(&JsValue::null(), "", Some(url))
+        })
+    }
+
+    pub fn back() {
+        window::history().back().unwrap_throw();
+    }
+
+    fn update_url_path(
+        path: impl Into<UrlPath>,
+        update_history: impl FnOnce(&web_sys::History, &str) -> Result<(), JsValue>,
+    ) {
         let path = path.into();
         let mut url = BASE_URI.with(String::clone);
         url.push_str(path.as_str());
 
         task::local::with(move |local| {
-            window::history()
-                .push_state_with_url(&JsValue::null(), "", Some(&url))
-                .unwrap_throw();
+            update_history(&window::history(), &url).unwrap_throw();
             local.router.0.set(path);
         });
     }
@@ -0,0 +1,258 @@
+use std::{collections::HashMap, str::FromStr};
+
+use futures_signals::signal::{Signal, SignalExt};
+
+use super::{url_path, UrlPath};
+
+/// A pattern to match URL paths against.
+///
+/// Patterns are made up of `'/'` separated segments, which can be:
+///
+/// - A literal, like `todos`, which must match exactly.
+/// - A named parameter, like `:id`, which matches any non-empty path
+///   component.
+/// - A wildcard, `*` or `*name`, which matches the rest of the path. This can
+///   only be the last segment.
+///
+/// Like [`UrlPath`], any leading `'/'`s are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use silkenweb::router::{Route, UrlPath};
+/// let route = Route::parse("/todos/:id/edit");
+/// let url_path = UrlPath::new("todos/42/edit?focus=title");
+/// let route_match = route.matches(&url_path).unwrap();
+///
+/// assert_eq!(route_match.param("id"), Some("42"));
+/// assert_eq!(route_match.parse::<u32>("id"), Some(42));
+/// assert_eq!(route_match.query("focus"), Some("title"));
+///
+/// assert!(route.matches(&UrlPath::new("todos/42")).is_none());
+/// assert!(route.matches(&UrlPath::new("todos//edit")).is_none());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Route {
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+    Wildcard(String),
+}
+
+impl Route {
+    /// Parse a route pattern.
+    ///
+    /// # Panics
+    ///
+    /// If a wildcard isn't the last segment, or a parameter has no name.
+    pub fn parse(pattern: &str) -> Self {
+        let pattern = pattern.trim_start_matches('/');
+        let mut segments = Vec::new();
+
+        if !pattern.is_empty() {
+            for segment in pattern.split('/') {
+                assert!(
+                    !matches!(segments.last(), Some(Segment::Wildcard(_))),
+                    "A wildcard must be the last segment in a route"
+                );
+
+                segments.push(if let Some(name) = segment.strip_prefix(':') {
+                    assert!(!name.is_empty(), "Route parameters must have a name");
+                    Segment::Param(name.to_string())
+                } else if let Some(name) = segment.strip_prefix('*') {
+                    Segment::Wildcard(name.to_string())
+                } else {
+                    Segment::Literal(segment.to_string())
+                });
+            }
+        }
+
+        Self { segments }
+    }
+
+    /// Match `url_path` against this route.
+    ///
+    /// Only the path is matched. The query string is available from the
+    /// result, and the hash is ignored.
+    pub fn matches<'a>(&'a self, url_path: &'a UrlPath) -> Option<RouteMatch<'a>> {
+        let mut components = url_path.path_components();
+        let mut values = Vec::with_capacity(self.segments.len());
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => {
+                    let value = components
+                        .next()
+                        .filter(|value| *value == literal.as_str())?;
+                    values.push(value);
+                }
+                Segment::Param(_) => {
+                    let value = components.next().filter(|value| !value.is_empty())?;
+                    values.push(value);
+                }
+                Segment::Wildcard(_) => {
+                    let matched_len: usize = values.iter().map(|value| value.len() + 1).sum();
+                    values.push(url_path.path().get(matched_len..).unwrap_or(""));
+
+                    return Some(RouteMatch {
+                        route: self,
+                        values,
+                        url_path,
+                    });
+                }
+            }
+        }
+
+        if components.next().is_some() {
+            return None;
+        }
+
+        Some(RouteMatch {
+            route: self,
+            values,
+            url_path,
+        })
+    }
+
+    /// A signal that's `Some` when the URL path matches this route.
+    ///
+    /// `f` converts the match into your route type. It can use `?` to return
+    /// `None` if a parameter doesn't parse, in which case the signal will be
+    /// `None`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use silkenweb::{prelude::*, router::Route};
+    /// struct EditTodo {
+    ///     id: u32,
+    /// }
+    ///
+    /// let edit_todo = Route::parse("todos/:id/edit")
+    ///     .signal(|route_match| Some(EditTodo {
+    ///         id: route_match.parse("id")?,
+    ///     }));
+    /// ```
+    pub fn signal<T>(
+        self,
+        mut f: impl FnMut(RouteMatch) -> Option<T> + 'static,
+    ) -> impl Signal<Item = Option<T>> + 'static
+    where
+        T: 'static,
+    {
+        url_path().signal_ref(move |url_path| self.matches(url_path).and_then(&mut f))
+    }
+
+    /// Build a path that matches this route, for example, to link to it.
+    ///
+    /// `params` gives the value of each named parameter and wildcard. An
+    /// unnamed wildcard matches an empty path. Values aren't percent encoded.
+    ///
+    /// This is `None` if a parameter is missing from `params`, or a parameter
+    /// value is empty or contains a `'/'`.
+    ///
+    /// ```
+    /// # use silkenweb::router::Route;
+    /// let route = Route::parse("todos/:id/*rest");
+    /// assert_eq!(
+    ///     route.path([("id", "42"), ("rest", "edit/title")]).as_deref(),
+    ///     Some("todos/42/edit/title")
+    /// );
+    /// assert!(route.path([("rest", "edit")]).is_none());
+    /// ```
+    pub fn path<'a>(&self, params: impl IntoIterator<Item = (&'a str, &'a str)>) -> Option<String> {
+        let params: HashMap<&str, &str> = params.into_iter().collect();
+        let mut components = Vec::with_capacity(self.segments.len());
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => components.push(literal.as_str()),
+                Segment::Param(name) => {
+                    let value = params
+                        .get(name.as_str())
+                        .filter(|value| !value.is_empty() && !value.contains('/'))?;
+                    components.push(*value);
+                }
+                Segment::Wildcard(name) if name.is_empty() => components.push(""),
+                Segment::Wildcard(name) => components.push(*params.get(name.as_str())?),
+            }
+        }
+
+        Some(components.join("/"))
+    }
+
+    fn param_index(&self, name: &str) -> Option<usize> {
+        self.segments.iter().position(|segment| match segment {
+            Segment::Param(param) | Segment::Wildcard(param) => param == name,
+            Segment::Literal(_) => false,
+        })
+    }
+}
+
+/// The result of a successful [`Route::matches`].
+pub struct RouteMatch<'a> {
+    route: &'a Route,
+    values: Vec<&'a str>,
+    url_path: &'a UrlPath,
+}
+
+impl<'a> RouteMatch<'a> {
+    /// The path component matched by the parameter `name`.
+    ///
+    /// Named wildcards can also be used, and will return the rest of the path.
+    /// The value isn't percent decoded.
+    pub fn param(&self, name: &str) -> Option<&'a str> {
+        self.route.param_index(name).map(|index| self.values[index])
+    }
+
+    /// Parse the path component matched by the parameter `name`.
+    ///
+    /// This is `None` if there's no parameter called `name`, or it doesn't
+    /// parse.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.param(name)?.parse().ok()
+    }
+
+    /// The rest of the path matched by a wildcard.
+    ///
+    /// ```
+    /// # use silkenweb::router::{Route, UrlPath};
+    /// let route = Route::parse("files/*");
+    /// let url_path = UrlPath::new("files/images/cat.png");
+    /// assert_eq!(route.matches(&url_path).unwrap().rest(), Some("images/cat.png"));
+    /// ```
+    pub fn rest(&self) -> Option<&'a str> {
+        match self.route.segments.last() {
+            Some(Segment::Wildcard(_)) => self.values.last().copied(),
+            _ => None,
+        }
+    }
+
+    /// The value of the first query parameter called `key`.
+    ///
+    /// Query parameters without a value, like `flag` in `path?flag`, have the
+    /// value `""`.
+    pub fn query(&self, key: &str) -> Option<&'a str> {
+        self.url_path
+            .query()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.unwrap_or(""))
+    }
+
+    /// Parse the value of the first query parameter called `key`.
+    ///
+    /// This is `None` if there's no parameter called `key`, or it doesn't
+    /// parse.
+    pub fn parse_query<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.query(key)?.parse().ok()
+    }
+
+    /// The URL path that was matched.
+    pub fn url_path(&self) -> &'a UrlPath {
+        self.url_path
+    }
+}
@@ -1,7 +1,11 @@
 //! Local and session storage.
+//!
+//! See [`idb`] for IndexedDB.
 use silkenweb_base::window;
 use wasm_bindgen::{JsValue, UnwrapThrowExt};
 
+pub mod idb;
+
 macro_rules! unexpected_exception {
     ($name:literal) => {
         concat!("`Storage::", $name, "`shouldn't throw")
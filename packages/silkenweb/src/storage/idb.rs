@@ -0,0 +1,226 @@
+//! A key/value store using IndexedDB.
+//!
+//! IndexedDB is asynchronous and can store much more than local storage, so
+//! it's suitable for offline data in larger apps.
+//!
+//! [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API)
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use futures::channel::oneshot;
+use futures_signals::signal::{Mutable, Signal};
+use silkenweb_base::{clone, window};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
+use web_sys::IdbTransactionMode;
+
+use crate::task::spawn_local;
+
+const STORE: &str = "key-value";
+
+/// An IndexedDB database, holding string values by key.
+///
+/// Clones refer to the same database, and share signals from
+/// [`Database::signal`].
+#[derive(Clone)]
+pub struct Database {
+    db: web_sys::IdbDatabase,
+    values: Rc<RefCell<HashMap<String, Mutable<Option<String>>>>>,
+}
+
+impl Database {
+    /// Open the database called `name`, creating it if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if IndexedDB isn't available, or the database
+    /// can't be opened. The [error value][mdn] will depend on the browser.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/IDBFactory/open#exceptions
+    pub async fn open(name: &str) -> Result<Self, JsValue> {
+        let factory =
+            window::indexed_db()?.ok_or_else(|| JsValue::from_str("IndexedDB isn't supported"))?;
+        let request = factory.open_with_u32(name, 1)?;
+        let on_upgrade_needed = Closure::<dyn FnMut()>::new({
+            clone!(request);
+            move || {
+                let db: web_sys::IdbDatabase = request.result().unwrap_throw().unchecked_into();
+                db.create_object_store(STORE).unwrap_throw();
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+        let result = request_result(&request).await;
+        request.set_onupgradeneeded(None);
+
+        Ok(Self {
+            db: result?.unchecked_into(),
+            values: Rc::default(),
+        })
+    }
+
+    /// Get the value associated with the key.
+    ///
+    /// # Errors
+    ///
+    /// The [error value][mdn] will depend on the browser.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/IDBObjectStore/get#exceptions
+    pub async fn get(&self, key: &str) -> Result<Option<String>, JsValue> {
+        let (_transaction, store) = self.store(IdbTransactionMode::Readonly)?;
+        let value = request_result(&store.get(&key.into())?).await?;
+
+        Ok(value.as_string())
+    }
+
+    /// Set the value associated with the key.
+    ///
+    /// This finishes once the value is stored, and any signals for `key` are
+    /// updated.
+    ///
+    /// # Errors
+    ///
+    /// If the storage is full, an error is returned. The [error value][mdn]
+    /// will depend on the browser.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/IDBObjectStore/put#exceptions
+    pub async fn insert(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        let (transaction, store) = self.store(IdbTransactionMode::Readwrite)?;
+        store.put_with_key(&value.into(), &key.into())?;
+        transaction_complete(&transaction).await?;
+        self.update_signal(key, Some(value.to_string()));
+
+        Ok(())
+    }
+
+    /// Remove a key/value pair.
+    ///
+    /// # Errors
+    ///
+    /// The [error value][mdn] will depend on the browser.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/IDBObjectStore/delete#exceptions
+    pub async fn remove(&self, key: &str) -> Result<(), JsValue> {
+        let (transaction, store) = self.store(IdbTransactionMode::Readwrite)?;
+        store.delete(&key.into())?;
+        transaction_complete(&transaction).await?;
+        self.update_signal(key, None);
+
+        Ok(())
+    }
+
+    /// A signal of the value associated with `key`.
+    ///
+    /// The signal is `None` until the value is read from the database, or if
+    /// there's no value. It reflects changes made with [`Database::insert`] and
+    /// [`Database::remove`] on this database or its clones, but not changes
+    /// made any other way, for example, from another tab.
+    pub fn signal(&self, key: &str) -> impl Signal<Item = Option<String>> {
+        let mut values = self.values.borrow_mut();
+
+        let value = values.entry(key.to_string()).or_insert_with(|| {
+            let value = Mutable::new(None);
+
+            spawn_local({
+                let db = self.clone();
+                let key = key.to_string();
+                clone!(value);
+
+                async move {
+                    if let Ok(initial) = db.get(&key).await {
+                        value.set_neq(initial);
+                    }
+                }
+            });
+
+            value
+        });
+
+        value.signal_cloned()
+    }
+
+    fn store(
+        &self,
+        mode: IdbTransactionMode,
+    ) -> Result<(web_sys::IdbTransaction, web_sys::IdbObjectStore), JsValue> {
+        let transaction = self.db.transaction_with_str_and_mode(STORE, mode)?;
+        let store = transaction.object_store(STORE)?;
+
+        Ok((transaction, store))
+    }
+
+    fn update_signal(&self, key: &str, value: Option<String>) {
+        if let Some(signal_value) = self.values.borrow().get(key) {
+            signal_value.set_neq(value);
+        }
+    }
+}
+
+/// Wait for a request to succeed or fail.
+async fn request_result(request: &web_sys::IdbRequest) -> Result<JsValue, JsValue> {
+    let (done, on_done) = oneshot::channel();
+    let callback = Callback::new(done);
+    request.set_onsuccess(Some(callback.as_function()));
+    request.set_onerror(Some(callback.as_function()));
+
+    let _clear = ClearOnDrop(|| {
+        request.set_onsuccess(None);
+        request.set_onerror(None);
+    });
+
+    on_done.await.unwrap_throw();
+
+    if let Some(error) = request.error()? {
+        return Err(error.into());
+    }
+
+    request.result()
+}
+
+/// Wait for a transaction to be committed or aborted.
+async fn transaction_complete(transaction: &web_sys::IdbTransaction) -> Result<(), JsValue> {
+    let (done, on_done) = oneshot::channel();
+    let callback = Callback::new(done);
+    transaction.set_oncomplete(Some(callback.as_function()));
+    transaction.set_onerror(Some(callback.as_function()));
+    transaction.set_onabort(Some(callback.as_function()));
+
+    let _clear = ClearOnDrop(|| {
+        transaction.set_oncomplete(None);
+        transaction.set_onerror(None);
+        transaction.set_onabort(None);
+    });
+
+    on_done.await.unwrap_throw();
+
+    match transaction.error() {
+        Some(error) => Err(error.into()),
+        None => Ok(()),
+    }
+}
+
+/// An event handler that signals `done` the first time it's called.
+struct Callback(Closure<dyn FnMut()>);
+
+impl Callback {
+    fn new(done: oneshot::Sender<()>) -> Self {
+        let mut done = Some(done);
+
+        Self(Closure::new(move || {
+            if let Some(done) = done.take() {
+                let _ = done.send(());
+            }
+        }))
+    }
+
+    fn as_function(&self) -> &js_sys::Function {
+        self.0.as_ref().unchecked_ref()
+    }
+}
+
+/// Remove event handlers if we stop waiting, so they aren't called after
+/// they're dropped.
+struct ClearOnDrop<F: FnMut()>(F);
+
+impl<F: FnMut()> Drop for ClearOnDrop<F> {
+    fn drop(&mut self) {
+        (self.0)()
+    }
+}
@@ -6,15 +6,32 @@
 //! via silkenweb are put on the render queue. See [requestAnimationFrame on
 //! MDN] for details.
 //!
+//! Updates are batched without any extra work. Setting a `Mutable` doesn't
+//! update the DOM straight away. It wakes the futures that depend on it, which
+//! run on the microtask queue once the current task, such as an event handler,
+//! has finished. Signals only yield their latest value, so setting several
+//! `Mutable`s, or the same one several times, in an event handler results in
+//! one update for each signal, before the browser renders.
+//!
 //! [microtask queue]: <https://developer.mozilla.org/en-US/docs/Web/API/HTML_DOM_API/Microtask_guide>
 //! [requestAnimationFrame on MDN]: <https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame>
-use std::cell::{Cell, RefCell};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
 
 use arch::{wait_for_microtasks, Raf};
+use discard::DiscardOnDrop;
 use futures::Future;
-use futures_signals::signal::{Mutable, Signal, SignalExt};
+use futures_signals::{
+    signal::{Mutable, Signal, SignalExt},
+    CancelableFutureHandle,
+};
 use silkenweb_macros::cfg_browser;
 
+use crate::node::element::spawn_cancelable_future;
+
 pub(crate) mod local;
 
 /// Spawn a future on the microtask queue.
@@ -25,6 +42,86 @@ where
     local::with(|local| local.task.runtime.spawn_local(future))
 }
 
+/// Spawn a future on the microtask queue, that's cancelled when `owner` is
+/// dropped.
+///
+/// Futures spawned with [`Element::spawn_future`] live as long as the element.
+/// This ties a future to any other state instead, by keeping a [`TaskOwner`]
+/// alongside it. For example, a store could own a background sync task.
+///
+/// # Example
+///
+/// ```no_run
+/// # use futures_signals::signal::{Mutable, SignalExt};
+/// # use silkenweb::task::{spawn_tied_to, TaskOwner};
+/// struct Store {
+///     items: Mutable<Vec<String>>,
+///     _tasks: TaskOwner,
+/// }
+///
+/// impl Store {
+///     fn new() -> Self {
+///         let items = Mutable::new(Vec::new());
+///         let tasks = TaskOwner::new();
+///
+///         spawn_tied_to(
+///             &tasks,
+///             items.signal_cloned().for_each(|items| async move {
+///                 // Sync `items` with the server
+///             }),
+///         );
+///
+///         Self {
+///             items,
+///             _tasks: tasks,
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`Element::spawn_future`]: crate::node::element::Element::spawn_future
+pub fn spawn_tied_to<F>(owner: &TaskOwner, future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    let mut tasks = owner.0.borrow_mut();
+    let id = tasks.next_id;
+    tasks.next_id += 1;
+
+    // Remove the handle once the future finishes, so finished futures don't
+    // accumulate while the owner is alive.
+    let owner = Rc::downgrade(&owner.0);
+    let handle = spawn_cancelable_future(async move {
+        future.await;
+
+        if let Some(tasks) = owner.upgrade() {
+            tasks.borrow_mut().handles.remove(&id);
+        }
+    });
+
+    tasks.handles.insert(id, handle);
+}
+
+/// Owns futures spawned with [`spawn_tied_to`].
+///
+/// Clones share ownership. The futures are cancelled when the last clone is
+/// dropped.
+#[derive(Clone, Default)]
+pub struct TaskOwner(Rc<RefCell<OwnedTasks>>);
+
+#[derive(Default)]
+struct OwnedTasks {
+    next_id: u64,
+    handles: HashMap<u64, DiscardOnDrop<CancelableFutureHandle>>,
+}
+
+impl TaskOwner {
+    /// Construct a [`TaskOwner`] with no futures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
 #[cfg_browser(false)]
 mod arch {
     use std::{cell::RefCell, future::Future};
@@ -154,6 +251,33 @@ pub async fn render_now() {
     Render::with(Render::render_effects);
 }
 
+/// Render pending updates until there are none left.
+///
+/// This is like [`render_now`], but it keeps going until rendering doesn't
+/// cause any more updates. Each round:
+///
+/// - waits for any tasks on the microtask queue, including any futures spawned
+///   to update the DOM from signals, then
+/// - runs any effects queued with [`on_animation_frame`], which includes DOM
+///   updates.
+///
+/// It finishes when there are no effects left after the microtask queue is
+/// empty. Timers, such as those from [`crate::time`], aren't waited for.
+///
+/// This never finishes if each round queues another effect, for example, if
+/// there's a running animation.
+pub async fn flush() {
+    loop {
+        wait_for_microtasks().await;
+
+        if !Render::with(Render::has_pending_effects) {
+            break;
+        }
+
+        Render::with(Render::render_effects);
+    }
+}
+
 /// Server tools.
 pub mod server;
 
@@ -231,6 +355,10 @@ impl Render {
         self.animation_timestamp_millis.get()
     }
 
+    fn has_pending_effects(&self) -> bool {
+        !self.pending_effects.borrow().is_empty()
+    }
+
     pub fn render_effects(&self) {
         for effect in self.pending_effects.take() {
             effect();
@@ -1,4 +1,13 @@
 //! Utilities for tacking time.
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_signals::signal::Signal;
+use pin_project::pin_project;
 use silkenweb_macros::cfg_browser;
 
 #[cfg_browser(true)]
@@ -26,6 +35,10 @@ mod arch {
         Sleep(gloo_timers::future::sleep(duration))
     }
 
+    pub fn rate_limit_timer(duration: Duration) -> Option<Sleep> {
+        Some(sleep(duration))
+    }
+
     #[derive(Debug)]
     #[pin_project]
     pub struct Interval(#[pin] IntervalStream);
@@ -76,6 +89,12 @@ mod arch {
         Sleep(tokio::time::sleep(duration))
     }
 
+    // There's nothing to wait for on the server, so values are yielded
+    // immediately.
+    pub fn rate_limit_timer(_duration: Duration) -> Option<Sleep> {
+        None
+    }
+
     #[derive(Debug)]
     #[pin_project]
     pub struct Interval(#[pin] Skip<IntervalStream>);
@@ -123,3 +142,205 @@ pub use arch::Interval;
 ///
 /// [`Future`]: std::future::Future
 pub use arch::Sleep;
+
+/// Debounce a signal, so it only yields once it's stopped changing.
+///
+/// A value is only yielded once the signal has been unchanged for `duration`,
+/// and it'll be the latest value. This includes the first value, so nothing is
+/// yielded until the signal has been quiet for `duration`. This is useful for
+/// things like search as you type, where only the final input matters.
+///
+/// The timer is reset on each change, and cancelled when the signal is
+/// dropped. If the underlying signal ends, any value that's waiting is yielded
+/// straight away.
+///
+/// On the server, every value is yielded immediately, as there's no point
+/// waiting when rendering.
+///
+/// # Panics
+///
+/// If `duration` can't be converted into a [`u32`] in milliseconds.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use html::{input, p, div, Div};
+/// # use silkenweb::{prelude::*, time::debounce};
+/// let search = Mutable::new(String::new());
+/// let query = debounce(search.signal_cloned(), Duration::from_millis(300));
+///
+/// # let d: Div =
+/// div()
+///     .child(input().on_input({
+///         clone!(search);
+///         move |_, input| search.set(input.value())
+///     }))
+///     .child(p().text(Sig(query.map(|query| format!("Searching for {query}")))));
+/// ```
+pub fn debounce<S: Signal>(signal: S, duration: Duration) -> Debounce<S> {
+    Debounce {
+        signal: Some(signal),
+        timer: None,
+        latest: None,
+        duration,
+    }
+}
+
+/// A signal that only yields once it's stopped changing.
+///
+/// See [`debounce`].
+#[must_use = "Signals do nothing unless polled"]
+#[pin_project]
+pub struct Debounce<S: Signal> {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    timer: Option<Sleep>,
+    latest: Option<S::Item>,
+    duration: Duration,
+}
+
+impl<S: Signal> Signal for Debounce<S> {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut proj = self.project();
+        let mut changed = false;
+
+        while let Some(signal) = proj.signal.as_mut().as_pin_mut() {
+            match signal.poll_change(cx) {
+                Poll::Ready(Some(value)) => {
+                    *proj.latest = Some(value);
+                    changed = true;
+                }
+                Poll::Ready(None) => proj.signal.set(None),
+                Poll::Pending => break,
+            }
+        }
+
+        if proj.latest.is_none() {
+            return if proj.signal.is_none() {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            };
+        }
+
+        if proj.signal.is_none() {
+            proj.timer.set(None);
+            return Poll::Ready(proj.latest.take());
+        }
+
+        if changed {
+            proj.timer.set(arch::rate_limit_timer(*proj.duration));
+        }
+
+        let timer_done = proj
+            .timer
+            .as_mut()
+            .as_pin_mut()
+            .map_or(true, |timer| timer.poll(cx).is_ready());
+
+        if timer_done {
+            proj.timer.set(None);
+            Poll::Ready(proj.latest.take())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Throttle a signal, so it yields at most once every `interval`.
+///
+/// The first value is yielded immediately. Any changes within `interval` of
+/// that are collapsed into the latest value, which is yielded once `interval`
+/// has passed. This is useful for signals driven by scroll or resize events.
+///
+/// A value is yielded immediately if the signal hasn't yielded for at least
+/// `interval`. The timer is cancelled when the signal is dropped, or once the
+/// underlying signal ends and there are no more values to yield.
+///
+/// On the server, every value is yielded immediately.
+///
+/// # Panics
+///
+/// If `interval` can't be converted into a [`u32`] in milliseconds.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use html::{div, Div};
+/// # use silkenweb::{prelude::*, time::throttle};
+/// let scroll_top = Mutable::new(0);
+///
+/// # let d: Div =
+/// div()
+///     .on_scroll({
+///         clone!(scroll_top);
+///         move |_, div| scroll_top.set(div.scroll_top())
+///     })
+///     .text(Sig(throttle(scroll_top.signal(), Duration::from_millis(100))
+///         .map(|y| format!("Scrolled to {y}"))));
+/// ```
+pub fn throttle<S: Signal>(signal: S, interval: Duration) -> Throttle<S> {
+    Throttle {
+        signal: Some(signal),
+        timer: None,
+        latest: None,
+        interval,
+    }
+}
+
+/// A signal that yields at most once per interval.
+///
+/// See [`throttle`].
+#[must_use = "Signals do nothing unless polled"]
+#[pin_project]
+pub struct Throttle<S: Signal> {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    timer: Option<Sleep>,
+    latest: Option<S::Item>,
+    interval: Duration,
+}
+
+impl<S: Signal> Signal for Throttle<S> {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut proj = self.project();
+
+        while let Some(signal) = proj.signal.as_mut().as_pin_mut() {
+            match signal.poll_change(cx) {
+                Poll::Ready(Some(value)) => *proj.latest = Some(value),
+                Poll::Ready(None) => proj.signal.set(None),
+                Poll::Pending => break,
+            }
+        }
+
+        let interval_elapsed = proj
+            .timer
+            .as_mut()
+            .as_pin_mut()
+            .map_or(true, |timer| timer.poll(cx).is_ready());
+
+        if interval_elapsed {
+            proj.timer.set(None);
+
+            if proj.latest.is_some() {
+                proj.timer.set(arch::rate_limit_timer(*proj.interval));
+                return Poll::Ready(proj.latest.take());
+            }
+        }
+
+        if proj.signal.is_none() && proj.latest.is_none() {
+            proj.timer.set(None);
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
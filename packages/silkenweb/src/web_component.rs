@@ -0,0 +1,317 @@
+//! Custom elements.
+//!
+//! Define a silkenweb component as a [custom element] with
+//! [`define_web_component!`], so it can be used from HTML or JavaScript like
+//! any other element.
+//!
+//! Each instance of the custom element gets an open shadow root. When an
+//! instance is connected to the document, [`WebComponent::render`] is called
+//! and the result is mounted in the shadow root. It's unmounted when the
+//! instance is disconnected. Observed attributes are available to
+//! [`WebComponent::render`] as signals, so the component can react to changes
+//! made from HTML or JavaScript.
+//!
+//! The rendered tree is dropped when an instance is disconnected, which
+//! cancels any effects and futures spawned with [`Element::spawn_future`], so
+//! hosts can add and remove instances without leaking them. If an instance is
+//! connected again, including after moving it to another document, it's
+//! rendered from scratch. Futures spawned with [`spawn_local`] aren't tied to
+//! the tree, so they won't be cancelled.
+//!
+//! Custom elements only exist in the browser, so defining one does nothing on
+//! the server.
+//!
+//! [custom element]: https://developer.mozilla.org/en-US/docs/Web/API/Web_components/Using_custom_elements
+//! [`Element::spawn_future`]: crate::node::element::Element::spawn_future
+//! [`spawn_local`]: crate::task::spawn_local
+use std::collections::HashMap;
+
+use futures_signals::signal::{Mutable, Signal};
+use silkenweb_macros::cfg_browser;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    dom::Wet,
+    node::element::{Const, GenericElement},
+};
+
+/// A component that can be defined as a custom element.
+///
+/// See [`define_web_component!`].
+pub trait WebComponent {
+    /// Render the shadow DOM for an instance of the custom element.
+    ///
+    /// This is called each time an instance is connected to the document.
+    /// `attributes` holds the attributes that were listed in
+    /// [`define_web_component!`].
+    fn render(attributes: &Attributes) -> GenericElement<Wet, Const>;
+}
+
+/// The observed attributes of a custom element instance.
+pub struct Attributes(HashMap<String, Mutable<Option<String>>>);
+
+impl Attributes {
+    /// A signal of the value of the attribute `name`.
+    ///
+    /// The value is `None` when the attribute isn't set.
+    ///
+    /// # Panics
+    ///
+    /// If `name` wasn't listed as an observed attribute in
+    /// [`define_web_component!`].
+    pub fn signal(&self, name: &str) -> impl Signal<Item = Option<String>> {
+        self.0
+            .get(name)
+            .unwrap_or_else(|| panic!("`{name}` isn't an observed attribute"))
+            .signal_cloned()
+    }
+}
+
+/// Define a custom element called `tag`, that renders a [`WebComponent`].
+///
+/// An optional list of attributes to observe can be given. Each of these is
+/// available as a signal from the [`Attributes`] passed to
+/// [`WebComponent::render`].
+///
+/// # Errors
+///
+/// An error is returned if `tag` isn't a [valid custom element name][mdn], or
+/// there's already a custom element called `tag`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use html::div;
+/// # use silkenweb::{
+/// #     define_web_component,
+/// #     dom::Wet,
+/// #     node::element::{Const, GenericElement},
+/// #     prelude::*,
+/// #     web_component::{Attributes, WebComponent},
+/// # };
+/// struct Greeting;
+///
+/// impl WebComponent for Greeting {
+///     fn render(attributes: &Attributes) -> GenericElement<Wet, Const> {
+///         div()
+///             .text(Sig(attributes.signal("name").map(|name| {
+///                 format!("Hello, {}!", name.as_deref().unwrap_or("world"))
+///             })))
+///             .into()
+///     }
+/// }
+///
+/// define_web_component!("hello-greeting", Greeting, ["name"]).unwrap();
+/// ```
+///
+/// `<hello-greeting name="Alice"></hello-greeting>` can now be used in HTML,
+/// and the greeting will update if the `name` attribute changes.
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/CustomElementRegistry/define#valid_custom_element_names
+#[macro_export]
+macro_rules! define_web_component {
+    ($tag:expr, $component:ty $(, [$($attribute:expr),* $(,)?])? $(,)?) => {
+        $crate::web_component::define::<$component>($tag, &[$($($attribute),*)?])
+    };
+}
+
+/// Define a custom element called `tag`.
+///
+/// This is what [`define_web_component!`] expands to.
+///
+/// # Errors
+///
+/// See [`define_web_component!`].
+pub fn define<T: WebComponent>(tag: &str, observed_attributes: &[&str]) -> Result<(), JsValue> {
+    arch::define(tag, observed_attributes, T::render)
+}
+
+#[cfg_browser(false)]
+mod arch {
+    use wasm_bindgen::JsValue;
+
+    use super::Attributes;
+    use crate::{
+        dom::Wet,
+        node::element::{Const, GenericElement},
+    };
+
+    pub fn define(
+        _tag: &str,
+        _observed_attributes: &[&str],
+        _render: fn(&Attributes) -> GenericElement<Wet, Const>,
+    ) -> Result<(), JsValue> {
+        Ok(())
+    }
+}
+
+#[cfg_browser(true)]
+mod arch {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use futures_signals::signal::Mutable;
+    use wasm_bindgen::{
+        prelude::{wasm_bindgen, Closure},
+        JsValue, UnwrapThrowExt,
+    };
+    use web_sys::{ShadowRootInit, ShadowRootMode};
+
+    use super::Attributes;
+    use crate::{
+        dom::Wet,
+        node::element::{Const, GenericElement},
+    };
+
+    #[wasm_bindgen(inline_js = r#"
+        export function define_custom_element(
+            tag,
+            observedAttributes,
+            connected,
+            disconnected,
+            attributeChanged
+        ) {
+            customElements.define(tag, class extends HTMLElement {
+                static get observedAttributes() {
+                    return observedAttributes;
+                }
+
+                // Moving an instance to another document disconnects it from
+                // the old one and connects it to the new one, so there's
+                // nothing extra to do in `adoptedCallback`.
+
+                connectedCallback() {
+                    // Callbacks are queued, so the instance may have been
+                    // removed again before this is called.
+                    if (this.isConnected && this.silkenwebInstance === undefined) {
+                        this.silkenwebInstance = connected(this);
+                    }
+                }
+
+                disconnectedCallback() {
+                    if (this.silkenwebInstance !== undefined) {
+                        disconnected(this.silkenwebInstance);
+                        delete this.silkenwebInstance;
+                    }
+                }
+
+                attributeChangedCallback(name, _oldValue, newValue) {
+                    // Attributes are read when the instance is connected, so
+                    // only changes after that need to be passed on.
+                    if (this.silkenwebInstance !== undefined) {
+                        attributeChanged(this.silkenwebInstance, name, newValue);
+                    }
+                }
+            });
+        }
+    "#)]
+    extern "C" {
+        #[wasm_bindgen(catch)]
+        fn define_custom_element(
+            tag: &str,
+            observed_attributes: &js_sys::Array,
+            connected: &JsValue,
+            disconnected: &JsValue,
+            attribute_changed: &JsValue,
+        ) -> Result<(), JsValue>;
+    }
+
+    pub fn define(
+        tag: &str,
+        observed_attributes: &[&str],
+        render: fn(&Attributes) -> GenericElement<Wet, Const>,
+    ) -> Result<(), JsValue> {
+        let observed_attributes: Vec<String> = observed_attributes
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let observed_attributes_array = observed_attributes
+            .iter()
+            .map(|name| JsValue::from_str(name))
+            .collect();
+        let connected = Closure::<dyn FnMut(web_sys::HtmlElement) -> u32>::new(
+            move |host: web_sys::HtmlElement| connect(&host, &observed_attributes, render),
+        );
+        let disconnected = Closure::<dyn FnMut(u32)>::new(disconnect);
+        let attribute_changed =
+            Closure::<dyn FnMut(u32, String, Option<String>)>::new(attribute_changed);
+
+        // Custom elements can't be undefined, so the callbacks are needed for the
+        // life of the page.
+        define_custom_element(
+            tag,
+            &observed_attributes_array,
+            &connected.into_js_value(),
+            &disconnected.into_js_value(),
+            &attribute_changed.into_js_value(),
+        )
+    }
+
+    fn connect(
+        host: &web_sys::HtmlElement,
+        observed_attributes: &[String],
+        render: fn(&Attributes) -> GenericElement<Wet, Const>,
+    ) -> u32 {
+        let attributes = Attributes(
+            observed_attributes
+                .iter()
+                .map(|name| (name.clone(), Mutable::new(host.get_attribute(name))))
+                .collect(),
+        );
+        let element = render(&attributes);
+        let shadow_root = host.shadow_root().unwrap_or_else(|| {
+            host.attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
+                .unwrap_throw()
+        });
+        shadow_root
+            .append_child(&element.dom_element())
+            .unwrap_throw();
+
+        INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            let id = instances.next_id;
+            instances.next_id += 1;
+            instances.mounted.insert(
+                id,
+                Instance {
+                    element,
+                    attributes,
+                },
+            );
+            id
+        })
+    }
+
+    fn disconnect(id: u32) {
+        let instance = INSTANCES.with(|instances| instances.borrow_mut().mounted.remove(&id));
+
+        // Dropping the element cancels any futures or effects it's running.
+        if let Some(instance) = instance {
+            instance.element.dom_element().remove();
+        }
+    }
+
+    fn attribute_changed(id: u32, name: String, value: Option<String>) {
+        INSTANCES.with(|instances| {
+            if let Some(instance) = instances.borrow().mounted.get(&id) {
+                if let Some(attribute) = instance.attributes.0.get(&name) {
+                    attribute.set_neq(value);
+                }
+            }
+        })
+    }
+
+    struct Instance {
+        element: GenericElement<Wet, Const>,
+        attributes: Attributes,
+    }
+
+    #[derive(Default)]
+    struct Instances {
+        next_id: u32,
+        mounted: HashMap<u32, Instance>,
+    }
+
+    thread_local! {
+        static INSTANCES: RefCell<Instances> = RefCell::default();
+    }
+}
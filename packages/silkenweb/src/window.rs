@@ -1,7 +1,9 @@
 //! Window utilities.
 
+use futures_signals::signal::Signal;
 use paste::paste;
 use silkenweb_base::Window;
+use silkenweb_macros::cfg_browser;
 use wasm_bindgen::JsCast;
 
 use crate::event::{bubbling_events, GlobalEventCallback};
@@ -67,6 +69,7 @@ events! {
     popstate: web_sys::PopStateEvent,
     rejectionhandled: web_sys::PromiseRejectionEvent,
     resize: web_sys::UiEvent,
+    scroll: web_sys::Event,
     storage: web_sys::StorageEvent,
     unhandledrejection: web_sys::PromiseRejectionEvent,
     unload: web_sys::Event,
@@ -78,3 +81,71 @@ events! {
 }
 
 bubbling_events!();
+
+/// A signal that's `true` while a CSS media query matches.
+///
+/// For example, `media_query_signal("print")` is `true` while the page is being
+/// printed, and `media_query_signal("(prefers-color-scheme: dark)")` tracks the
+/// user's color scheme. To run code just before or after printing, see
+/// [`on_beforeprint`] and [`on_afterprint`].
+///
+/// On non-WASM targets, the signal is always `false`.
+///
+/// # Panics
+///
+/// On WASM targets, this panics if `query` can't be parsed.
+pub fn media_query_signal(query: &str) -> impl Signal<Item = bool> + 'static {
+    arch::media_query_signal(query)
+}
+
+#[cfg_browser(false)]
+mod arch {
+    use futures_signals::signal::{always, Signal};
+
+    pub fn media_query_signal(_query: &str) -> impl Signal<Item = bool> + 'static {
+        always(false)
+    }
+}
+
+#[cfg_browser(true)]
+mod arch {
+    use futures_signals::signal::{Mutable, Signal, SignalExt};
+    use silkenweb_base::{clone, window};
+    use wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt};
+
+    pub fn media_query_signal(query: &str) -> impl Signal<Item = bool> + 'static {
+        let list = window::match_media(query)
+            .unwrap_throw()
+            .expect_throw("Couldn't parse media query");
+        let matches = Mutable::new(list.matches());
+        let on_change = Closure::<dyn FnMut()>::new({
+            clone!(list, matches);
+            move || matches.set_neq(list.matches())
+        });
+        list.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())
+            .unwrap_throw();
+        let listener = Listener { list, on_change };
+
+        matches.signal().map(move |matches| {
+            // Keep the listener alive as long as the signal.
+            let _listener = &listener;
+            matches
+        })
+    }
+
+    struct Listener {
+        list: web_sys::MediaQueryList,
+        on_change: Closure<dyn FnMut()>,
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            self.list
+                .remove_event_listener_with_callback(
+                    "change",
+                    self.on_change.as_ref().unchecked_ref(),
+                )
+                .unwrap_throw();
+        }
+    }
+}
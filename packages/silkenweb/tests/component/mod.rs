@@ -26,3 +26,56 @@ fn component() {
         r#"<div><template shadowroot="open"><style>* { color: red }</style><div><div><slot name="0"></slot></div><div><slot name="1"></slot></div></div></template><span slot="0">slot 0</span><span slot="1">slot 1</span></div>"#
     );
 }
+
+#[cfg(feature = "declarative-shadow-dom")]
+#[test]
+fn component_parts() {
+    use silkenweb::{
+        dom::Dry,
+        elements::HtmlElement,
+        node::{
+            element::{Const, GenericElement},
+            Component,
+        },
+        prelude::{html::button, ParentElement},
+    };
+
+    let comp: GenericElement<Dry, Const> = Component::<Dry>::new()
+        .child(button().parts(["button"]).text("OK"))
+        .export_parts([("button", "ok-button")])
+        .into();
+
+    assert_eq!(
+        comp.to_string(),
+        r#"<div exportparts="button:ok-button"><template shadowroot="open"><button part="button">OK</button></template></div>"#
+    );
+}
+
+#[cfg(feature = "declarative-shadow-dom")]
+#[test]
+fn component_default_slot() {
+    use silkenweb::{
+        dom::Dry,
+        node::{
+            element::{Const, GenericElement},
+            Component,
+        },
+        prelude::{
+            html::{div, span},
+            ParentElement,
+        },
+    };
+
+    let mut comp = Component::<Dry>::new();
+    let named_slot = comp.slot(span().text("named"));
+    let default_slot = comp.default_slot([span().text("default")]);
+
+    let comp: GenericElement<Dry, Const> = comp
+        .child(div().child(named_slot).child(default_slot.text("fallback")))
+        .into();
+
+    assert_eq!(
+        comp.to_string(),
+        r#"<div><template shadowroot="open"><div><slot name="0"></slot><slot>fallback</slot></div></template><span slot="0">named</span><span>default</span></div>"#
+    );
+}
@@ -0,0 +1,145 @@
+use futures_signals::signal::Mutable;
+use silkenweb::{
+    document::{Document, PreloadAs},
+    dom::DefaultDom,
+    elements::html::{div, p},
+    mount,
+    node::element::Element,
+    prelude::{HtmlElement, ParentElement},
+    task::render_now,
+    value::Sig,
+};
+use silkenweb_test::{html_element, BrowserTest};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::APP_ID;
+
+isomorphic_test! {
+    async fn head_nonce() {
+        DefaultDom::unmount_all();
+        DefaultDom::set_nonce(Some("my-nonce".to_string()));
+        DefaultDom::mount_in_head("with-nonce", div());
+        DefaultDom::set_nonce(None);
+        DefaultDom::mount_in_head("without-nonce", div());
+
+        let head_html = DefaultDom::head_inner_html();
+        assert!(head_html.contains(r#"<div id="with-nonce" nonce="my-nonce"></div>"#));
+        assert!(head_html.contains(r#"<div id="without-nonce"></div>"#));
+        DefaultDom::unmount_all();
+    }
+}
+
+isomorphic_test! {
+    async fn resource_hints() {
+        DefaultDom::unmount_all();
+        assert!(DefaultDom::preconnect("https://example.com"));
+        assert!(DefaultDom::preload("/font.woff2", PreloadAs::Font));
+        assert!(!DefaultDom::preload("/font.woff2", PreloadAs::Font));
+
+        let head_html = DefaultDom::head_inner_html();
+        assert!(head_html.contains(
+            r#"<link rel="preconnect" href="https://example.com" id="silkenweb-preconnect-https://example.com">"#
+        ));
+        assert!(head_html.contains(
+            r#"<link rel="preload" href="/font.woff2" as="font" crossorigin="anonymous" id="silkenweb-preload-/font.woff2">"#
+        ));
+        DefaultDom::unmount_all();
+    }
+}
+
+#[wasm_bindgen_test]
+async fn mount_to_element() {
+    let test = BrowserTest::new(APP_ID).await;
+
+    let mount_handle = DefaultDom::mount_to(&html_element(APP_ID), p().text("Hello"));
+    render_now().await;
+    assert_eq!(r#"<p>Hello</p>"#, test.html());
+    mount_handle.unmount();
+    assert_eq!(r#"<div id="app"></div>"#, test.html());
+}
+
+#[wasm_bindgen_test]
+async fn mount_append() {
+    let test = BrowserTest::new(APP_ID).await;
+    html_element(APP_ID).set_inner_html("<span>Existing</span>");
+
+    let mount_handle = DefaultDom::mount_append(APP_ID, p().text("Appended"));
+    render_now().await;
+    assert_eq!(
+        r#"<div id="app"><span>Existing</span><p>Appended</p></div>"#,
+        test.html()
+    );
+
+    let mount_handle = mount_handle.unmount().unwrap().remount();
+    assert_eq!(
+        r#"<div id="app"><span>Existing</span><p>Appended</p></div>"#,
+        test.html()
+    );
+
+    mount_handle.unmount();
+    assert_eq!(
+        r#"<div id="app"><span>Existing</span></div>"#,
+        test.html(),
+        "Only the appended element is removed"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn remount() {
+    let test = BrowserTest::new(APP_ID).await;
+
+    let text = Mutable::new("Hello");
+    let mount_handle = mount(APP_ID, p().id(APP_ID).text(Sig(text.signal())));
+    render_now().await;
+
+    let unmounted = mount_handle.unmount().unwrap();
+    assert_eq!(r#"<div id="app"></div>"#, test.html());
+
+    let mount_handle = unmounted.remount();
+    text.set("Goodbye");
+    render_now().await;
+    assert_eq!(
+        r#"<p id="app">Goodbye</p>"#,
+        test.html(),
+        "The same element is mounted again, so it's still reactive"
+    );
+
+    mount_handle.unmount();
+    assert_eq!(r#"<div id="app"></div>"#, test.html());
+}
+
+#[wasm_bindgen_test]
+async fn mount_hooks() {
+    let test = BrowserTest::new(APP_ID).await;
+    let is_mounted = Mutable::new(false);
+
+    let mount_handle = mount(
+        APP_ID,
+        p().id(APP_ID)
+            .on_mount({
+                let is_mounted = is_mounted.clone();
+                move || is_mounted.set(true)
+            })
+            .on_unmount({
+                let is_mounted = is_mounted.clone();
+                move || is_mounted.set(false)
+            }),
+    );
+    assert!(
+        !is_mounted.get(),
+        "`on_mount` is called after the next render"
+    );
+
+    render_now().await;
+    assert!(is_mounted.get());
+    assert_eq!(r#"<p id="app"></p>"#, test.html());
+
+    let unmounted = mount_handle.unmount();
+    assert!(
+        is_mounted.get(),
+        "The unmounted element is kept alive so it can be remounted"
+    );
+
+    drop(unmounted);
+    assert!(!is_mounted.get());
+}
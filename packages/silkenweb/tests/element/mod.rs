@@ -1,25 +1,34 @@
+use std::{cell::Cell, future, rc::Rc};
+
+use futures::StreamExt;
 use futures_signals::{
-    signal::Mutable,
+    signal::{always, Mutable, SignalExt},
     signal_vec::{MutableVec, MutableVecLockMut, SignalVecExt},
 };
 use silkenweb::{
-    dom::DefaultDom,
+    dom::{DefaultDom, Dry},
     elements::{
         html::{div, p, Div},
-        HtmlElement,
+        ElementEvents, HtmlElement, PopoverMode,
+    },
+    node::{
+        element::{Const, Element, GenericElement, Namespace},
+        text, Fragment, Node,
     },
-    node::{element::Element, text, Node},
     prelude::ParentElement,
-    task::render_now,
+    task::{flush, on_animation_frame, render_now},
     value::Sig,
 };
 use silkenweb_macros::cfg_browser;
 
 macro_rules! render_test {
     ($name:ident, $node:expr, $expected:expr) => {
+        render_test!($name, DefaultDom, $node, $expected);
+    };
+    ($name:ident, $dom:ty, $node:expr, $expected:expr) => {
         isomorphic_test! {
             async fn $name() {
-                assert_eq!(Node::<DefaultDom>::from($node).to_string(), $expected)
+                assert_eq!(Node::<$dom>::from($node).to_string(), $expected)
             }
         }
     };
@@ -43,11 +52,86 @@ render_test!(
     div().id("my-id").class("my-class"),
     r#"<div id="my-id" class="my-class"></div>"#
 );
+render_test!(
+    primitive_attributes,
+    div()
+        .attribute("data-count", 3usize)
+        .attribute("data-key", 'k')
+        .attribute("data-name", std::rc::Rc::<str>::from("name")),
+    r#"<div data-count="3" data-key="k" data-name="name"></div>"#
+);
+render_test!(
+    popover_attribute,
+    div().popover(PopoverMode::Manual),
+    r#"<div popover="manual"></div>"#
+);
+render_test!(
+    static_classes,
+    div().classes(["a", "b", "c"]),
+    r#"<div class="a b c"></div>"#
+);
+render_test!(
+    toggle_attribute,
+    div()
+        .toggle_attribute("data-on", true)
+        .toggle_attribute("data-off", false),
+    r#"<div data-on=""></div>"#
+);
+render_test!(
+    inert_attribute,
+    div().inert(true),
+    r#"<div inert=""></div>"#
+);
+render_test!(
+    child_in_slot,
+    div().child_in_slot("foo", p().text("Hello!")),
+    r#"<div><p slot="foo">Hello!</p></div>"#
+);
+mod manifest {
+    silkenweb::custom_elements_manifest!("tests/element/custom-elements.json");
+}
+
+render_test!(
+    custom_elements_manifest,
+    manifest::test_button()
+        .accessible_name("Save")
+        .design(manifest::TestButtonDesign::Emphasized)
+        .disabled(true)
+        .r#type("submit")
+        .on_click(|_, _| ())
+        .on_selection_change(|_, _| ())
+        .text("Save"),
+    r#"<test-button accessible-name="Save" design="Emphasized" disabled="" type="submit">Save</test-button>"#
+);
+render_test!(
+    dangerous_inner_html,
+    div().dangerous_inner_html("<p>Hello, <em>world</em>!</p>"),
+    "<div><p>Hello, <em>world</em>!</p></div>"
+);
+render_test!(
+    fragment_children,
+    div()
+        .text("Start")
+        .children(Fragment::new().child(p().text("1")).child(p().text("2")))
+        .text("End"),
+    "<div>Start<p>1</p><p>2</p>End</div>"
+);
+render_test!(
+    class_set,
+    div().classes(silkenweb::class_set!["a", "b", "a"]),
+    r#"<div class="a b"></div>"#
+);
 render_test!(
     boolean_false_attribute,
     div().hidden(false),
     r#"<div></div>"#
 );
+render_test!(
+    dry_boolean_false_attribute,
+    Dry,
+    div().hidden(false),
+    r#"<div></div>"#
+);
 render_test!(
     boolean_true_attribute,
     div().hidden(true),
@@ -65,6 +149,199 @@ render_test!(
     "<div><p>Hello</p><p>World!</p></div>"
 );
 
+isomorphic_test! {
+    async fn on_unmount() {
+        let is_mounted = Mutable::new(true);
+        let children = MutableVec::new_with_values(vec![()]);
+        let elem: Node = div()
+            .children_signal(children.signal_vec().map({
+                let is_mounted = is_mounted.clone();
+                move |()| {
+                    let is_mounted = is_mounted.clone();
+                    p().on_unmount(move || is_mounted.set(false))
+                }
+            }))
+            .into();
+
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><p></p></div>");
+        assert!(is_mounted.get());
+
+        children.lock_mut().clear();
+        flush().await;
+        assert_eq!(elem.to_string(), "<div></div>");
+        assert!(!is_mounted.get());
+    }
+}
+
+isomorphic_test! {
+    async fn bind_attribute() {
+        let selected = Mutable::new("first".to_string());
+        let elem: Div = div().bind_attribute("data-selected", selected.clone());
+        let handle = elem.handle();
+        let elem: Node = elem.into();
+
+        render_now().await;
+        assert_eq!(elem.to_string(), r#"<div data-selected="first"></div>"#);
+
+        selected.set("second".to_string());
+        render_now().await;
+        assert_eq!(elem.to_string(), r#"<div data-selected="second"></div>"#);
+
+        // Attribute changes can only be observed in the browser.
+        if let Some(dom_elem) = handle.try_dom_element() {
+            dom_elem.set_attribute("data-selected", "third").unwrap();
+            render_now().await;
+            assert_eq!(selected.get_cloned(), "third");
+            assert_eq!(elem.to_string(), r#"<div data-selected="third"></div>"#);
+        }
+    }
+}
+
+isomorphic_test! {
+    async fn spawn_future_dropped_with_element() {
+        struct DropCounter(Rc<Cell<usize>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1)
+            }
+        }
+
+        let drop_count = Rc::new(Cell::new(0));
+        let children = MutableVec::new_with_values(vec![()]);
+        let elem: Node = div()
+            .children_signal(children.signal_vec().map({
+                let drop_count = drop_count.clone();
+                move |()| {
+                    let drop_counter = DropCounter(drop_count.clone());
+
+                    p().spawn_future(async move {
+                        let _drop_counter = drop_counter;
+                        future::pending().await
+                    })
+                }
+            }))
+            .into();
+
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><p></p></div>");
+        assert_eq!(drop_count.get(), 0);
+
+        children.lock_mut().clear();
+        flush().await;
+        assert_eq!(elem.to_string(), "<div></div>");
+        assert_eq!(drop_count.get(), 1, "The future is dropped with its element");
+    }
+}
+
+isomorphic_test! {
+    async fn visible_signal() {
+        let elem = div();
+        let is_visible = elem.handle().visible_signal();
+        let _elem: Node = elem.into();
+
+        render_now().await;
+        assert_eq!(
+            is_visible.to_stream().next().await,
+            Some(false),
+            "The element isn't in the document, so it's not visible"
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn size_signal() {
+        let elem = div();
+        let size = elem.handle().size_signal();
+        let _elem: Node = elem.into();
+
+        render_now().await;
+        assert_eq!(size.to_stream().next().await, Some((0.0, 0.0)));
+    }
+}
+
+isomorphic_test! {
+    async fn dangerous_inner_html_signal() {
+        let html = Mutable::new("<p>Loading</p>");
+        let elem: Node = div().dangerous_inner_html(Sig(html.signal())).into();
+
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><p>Loading</p></div>");
+
+        html.set("<ul><li>Loaded</li></ul>");
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><ul><li>Loaded</li></ul></div>");
+    }
+}
+
+macro_rules! inner_html_children_test {
+    ($name:ident, |$elem:ident| $add_children:expr) => {
+        #[cfg_browser(false)]
+        #[test]
+        #[should_panic(
+            expected = "Children can't be added to an element with `dangerous_inner_html`"
+        )]
+        fn $name() {
+            silkenweb::task::server::sync_scope(|| {
+                let $elem: Div = div().dangerous_inner_html("<p>Hello</p>");
+                let _elem: Div = $add_children;
+            })
+        }
+    };
+}
+
+inner_html_children_test!(inner_html_then_children, |elem| elem.children([p()]));
+inner_html_children_test!(inner_html_then_child_signal, |elem| {
+    elem.child_signal(always(p()))
+});
+inner_html_children_test!(inner_html_then_children_signal, |elem| {
+    elem.children_signal(
+        MutableVec::new_with_values(vec![1])
+            .signal_vec()
+            .map(|_| p()),
+    )
+});
+inner_html_children_test!(inner_html_then_keyed_children, |elem| {
+    elem.keyed_children(
+        MutableVec::new_with_values(vec![1]).signal_vec(),
+        |i| *i,
+        |_| p(),
+    )
+});
+
+isomorphic_test! {
+    async fn namespace() {
+        let html: GenericElement = div().into();
+        let svg: GenericElement = GenericElement::new(Namespace::Svg, "svg");
+        let other: GenericElement = GenericElement::new(Namespace::Other("urn:test"), "item");
+
+        assert_eq!(html.namespace(), Namespace::Html);
+        assert_eq!(svg.namespace(), Namespace::Svg);
+        assert_eq!(other.namespace(), Namespace::Other("urn:test"));
+    }
+}
+
+silkenweb::custom_html_element!(foreign_item = {
+    namespace = Namespace::Other("urn:silkenweb:test");
+    dom_type: web_sys::Element;
+    attributes { label: String };
+});
+
+silkenweb::parent_element!(foreign_item);
+
+isomorphic_test! {
+    async fn custom_namespace() {
+        let item: GenericElement = foreign_item().label("Item").text("Hello").into();
+
+        assert_eq!(item.namespace(), Namespace::Other("urn:silkenweb:test"));
+        assert_eq!(
+            item.freeze().to_string(),
+            r#"<foreign-item label="Item">Hello</foreign-item>"#
+        );
+    }
+}
+
 // Make sure the test is actually run
 #[cfg_browser(false)]
 #[test]
@@ -293,6 +570,350 @@ isomorphic_test! {
     }
 }
 
+isomorphic_test! {
+    async fn boolean_attribute_signal() {
+        let hidden = Mutable::new(true);
+        let elem: Node = div().hidden(Sig(hidden.signal())).into();
+        render_now().await;
+        assert_eq!(elem.to_string(), r#"<div hidden=""></div>"#);
+        hidden.set(false);
+        render_now().await;
+        assert_eq!(elem.to_string(), r#"<div></div>"#);
+        hidden.set(true);
+        render_now().await;
+        assert_eq!(elem.to_string(), r#"<div hidden=""></div>"#);
+    }
+}
+
+isomorphic_test! {
+    async fn child_signal() {
+        let first = Mutable::new(true);
+        let elem: Node = div()
+            .child_signal(first.signal().map(|first| -> Node {
+                if first {
+                    p().text("First").into()
+                } else {
+                    div().text("Second").into()
+                }
+            }))
+            .into();
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><p>First</p></div>");
+        first.set(false);
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><div>Second</div></div>");
+    }
+}
+
+isomorphic_test! {
+    async fn optional_child_node() {
+        let count = Mutable::new(1);
+        let elem: Node = div()
+            .text("Start")
+            .optional_child(Sig(count.signal().map(|count| -> Option<Node> {
+                match count {
+                    0 => None,
+                    1 => Some(p().text("One").into()),
+                    _ => Some(div().text("Many").into()),
+                }
+            })))
+            .text("End")
+            .into();
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div>Start<p>One</p>End</div>");
+        count.set(2);
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div>Start<div>Many</div>End</div>");
+        count.set(0);
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div>StartEnd</div>");
+        count.set(1);
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div>Start<p>One</p>End</div>");
+    }
+}
+
+isomorphic_test! {
+    async fn fragment_signal() {
+        let count = Mutable::new(2);
+        let elem: Node = div()
+            .text("Start")
+            .children_signal(
+                count
+                    .signal()
+                    .map(|count| {
+                        (0..count)
+                            .map(|i| p().text(format!("{i}")))
+                            .collect::<Fragment>()
+                            .into_iter()
+                            .collect::<Vec<_>>()
+                    })
+                    .to_signal_vec(),
+            )
+            .into();
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div>Start<p>0</p><p>1</p></div>");
+        count.set(1);
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div>Start<p>0</p></div>");
+        count.set(0);
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div>Start</div>");
+    }
+}
+
+isomorphic_test! {
+    async fn children_signal_keyed() {
+        let items = Mutable::new(vec![(1, "a"), (2, "b"), (3, "c")]);
+        let elem: Node = div()
+            .children_signal_keyed(
+                items.signal_cloned().to_signal_vec(),
+                |(key, _)| *key,
+                |(key, text)| p().id(key.to_string()).text(text),
+            )
+            .into();
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<div><p id="1">a</p><p id="2">b</p><p id="3">c</p></div>"#
+        );
+
+        // Existing children are kept, so their text doesn't change.
+        items.set(vec![(3, "x"), (4, "d"), (1, "x")]);
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<div><p id="3">c</p><p id="4">d</p><p id="1">a</p></div>"#
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn keyed_children() {
+        let items = Mutable::new(vec![(1, "a"), (2, "b"), (3, "c")]);
+        let elem: Node = div()
+            .keyed_children(
+                items.signal_cloned().to_signal_vec(),
+                |(key, _)| *key,
+                |item| {
+                    let (key, _) = item.get();
+                    p().id(key.to_string())
+                        .text(Sig(item.signal_ref(|(_, text)| *text)))
+                },
+            )
+            .into();
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<div><p id="1">a</p><p id="2">b</p><p id="3">c</p></div>"#
+        );
+
+        // Existing children are kept, and updated from their signal.
+        items.set(vec![(3, "x"), (4, "d"), (1, "y")]);
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<div><p id="3">x</p><p id="4">d</p><p id="1">y</p></div>"#
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn write_html() {
+        let elem: GenericElement<DefaultDom, Const> = div()
+            .id("outer")
+            .attribute("data-escaped", "\"quoted\" & <tagged>")
+            .child(p().class("greeting").text("Hello"))
+            .child(text("<world> & friends!"))
+            .into();
+        render_now().await;
+
+        let mut html = String::new();
+        elem.write_html(&mut html).unwrap();
+        assert_eq!(html, elem.to_string());
+    }
+}
+
+isomorphic_test! {
+    async fn render_html() {
+        let greeting = Mutable::new("Hello");
+        let elem: GenericElement<DefaultDom, Const> = div()
+            .id("outer")
+            .child(p().class("greeting").text(Sig(greeting.signal())))
+            .child(text("world!"))
+            .into();
+        let mut html = String::new();
+        elem.render_html(&mut html).await.unwrap();
+        assert_eq!(html, elem.to_string());
+        assert_eq!(
+            html,
+            r#"<div id="outer"><p class="greeting">Hello</p>world!</div>"#
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn render_to_string() {
+        let text = Mutable::new("Before");
+        let elem: GenericElement<DefaultDom, Const> = div().text(Sig(text.signal())).into();
+        // Updates from effects are rendered as well.
+        on_animation_frame(move || text.set("After"));
+        assert_eq!(elem.render_to_string().await, "<div>After</div>");
+    }
+}
+
+#[cfg_browser(false)]
+#[test]
+fn render_with_head() {
+    use silkenweb::{
+        document::Document,
+        dom::Dry,
+        elements::html::style,
+        task::server::{block_on, scope},
+    };
+
+    fn styled_p(text: &str) -> silkenweb::elements::html::P<Dry> {
+        Dry::mount_in_head("p-style", style().text("p {color: red}"));
+        p().text(text)
+    }
+
+    block_on(scope(async {
+        let show = Mutable::new(false);
+        let elem: GenericElement<Dry, Const> = div()
+            .optional_child(Sig(show.signal().map(|show| show.then(|| styled_p("1")))))
+            .optional_child(Sig(show.signal().map(|show| show.then(|| styled_p("2")))))
+            .into();
+        on_animation_frame(move || show.set(true));
+
+        let html = elem.render_with_head().await;
+        assert_eq!(html.body, "<div><p>1</p><p>2</p></div>");
+        assert_eq!(html.head, r#"<style id="p-style">p {color: red}</style>"#);
+    }));
+}
+
+#[cfg_browser(false)]
+#[test]
+fn dedupe_text() {
+    use std::{cell::Cell, rc::Rc};
+
+    use silkenweb::{
+        dom::Dry,
+        task::server::{block_on, scope},
+        value::RefSignalOrValue,
+    };
+
+    block_on(scope(async {
+        let text = Mutable::new("Hello");
+        let set_text_count = Rc::new(Cell::new(0));
+        let elem: GenericElement<Dry, Const> = div()
+            .text(Sig(text.signal()).dedupe().map({
+                let set_text_count = set_text_count.clone();
+                move |text| {
+                    set_text_count.set(set_text_count.get() + 1);
+                    text
+                }
+            }))
+            .into();
+
+        render_now().await;
+        text.set("Hello");
+        render_now().await;
+        text.set("Hello");
+        render_now().await;
+        assert_eq!(set_text_count.get(), 1);
+
+        text.set("World");
+        render_now().await;
+        assert_eq!(set_text_count.get(), 2);
+        assert_eq!(elem.to_string(), "<div>World</div>");
+    }));
+}
+
+isomorphic_test! {
+    async fn text_updates_coalesce() {
+        use std::{cell::Cell, rc::Rc};
+
+        use silkenweb::value::RefSignalOrValue;
+
+        let count = Mutable::new(0);
+        let write_count = Rc::new(Cell::new(0));
+        let elem: GenericElement<DefaultDom, Const> = div()
+            .text(Sig(count.signal()).map({
+                let write_count = write_count.clone();
+                move |count| {
+                    write_count.set(write_count.get() + 1);
+                    count.to_string()
+                }
+            }))
+            .into();
+
+        render_now().await;
+        assert_eq!(write_count.get(), 1);
+
+        // Signals only yield their latest value, so the text is only written once.
+        count.set(1);
+        count.set(2);
+        count.set(3);
+        render_now().await;
+        assert_eq!(write_count.get(), 2);
+        assert_eq!(elem.to_string(), "<div>3</div>");
+    }
+}
+
+#[cfg_browser(false)]
+#[test]
+fn container_style() {
+    use silkenweb::{node::element::ContainerType, task::server::sync_scope};
+
+    sync_scope(|| {
+        let elem = div()
+            .container_type(ContainerType::InlineSize)
+            .container_name("sidebar");
+
+        // Browsers may serialize this using the `container` shorthand, so we only
+        // test on the server.
+        assert_eq!(
+            elem.freeze().to_string(),
+            r#"<div style="container-type: inline-size; container-name: sidebar;"></div>"#
+        );
+    })
+}
+
+#[cfg_browser(false)]
+#[test]
+fn anchor_position_style() {
+    use silkenweb::{node::element::AnchorSide, task::server::sync_scope};
+
+    sync_scope(|| {
+        let anchor = div().anchor_name("--anchor");
+        let elem = div().position_anchor("--anchor", anchor.handle(), AnchorSide::Bottom);
+
+        // Browsers without anchor positioning drop the properties they don't
+        // understand, so we only test on the server.
+        assert_eq!(
+            anchor.freeze().to_string(),
+            r#"<div style="anchor-name: --anchor;"></div>"#
+        );
+        assert_eq!(
+            elem.freeze().to_string(),
+            r#"<div style="position: fixed; position-anchor: --anchor; top: anchor(bottom); left: anchor(left);"></div>"#
+        );
+    })
+}
+
+isomorphic_test! {
+    async fn node_element_round_trip() {
+        let node: Node = div().id("my-div").child(p().text("Hello!")).into();
+        let elem = GenericElement::<DefaultDom, Const>::try_from(node).ok().unwrap();
+        let node: Node = elem.into();
+        assert_eq!(node.to_string(), r#"<div id="my-div"><p>Hello!</p></div>"#);
+
+        let node: Node = text("Hello!").into();
+        let node = GenericElement::<DefaultDom, Const>::try_from(node).err().unwrap();
+        assert_eq!(node.to_string(), "Hello!");
+    }
+}
+
 isomorphic_test! {
     async fn text_node() {
         let elem: Node = div().child(text("Hello, world!")).into();
@@ -0,0 +1,219 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use futures_signals::signal::Mutable;
+use silkenweb::{
+    document,
+    elements::html::{button, div, input, p},
+    keyboard::{Key, Mods},
+    mount,
+    node::element::{Element, EventListenerOptions, FocusOptions, ParentElement},
+    prelude::{ElementEvents, HtmlElement},
+    task::render_now,
+    value::Sig,
+};
+use silkenweb_test::{click, type_text, BrowserTest};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::APP_ID;
+
+#[wasm_bindgen_test]
+async fn click_and_type() {
+    let test = BrowserTest::new(APP_ID).await;
+    let clicked = Mutable::new(false);
+    let text = Mutable::new(String::new());
+
+    mount(
+        APP_ID,
+        div()
+            .child(button().id("button").on_click({
+                let clicked = clicked.clone();
+                move |_, _| clicked.set(true)
+            }))
+            .child(input().id("input").on_input({
+                let text = text.clone();
+                move |_, input| text.set(input.value())
+            }))
+            .child(p().id("text").text(Sig(text.signal_cloned()))),
+    );
+    render_now().await;
+
+    click(&test.query("#button").unwrap()).await;
+    assert!(clicked.get());
+
+    type_text(&test.query("#input").unwrap(), "abc").await;
+    assert_eq!(text.get_cloned(), "abc");
+    assert_eq!(test.query("#text").unwrap().inner_text(), "abc");
+}
+
+#[wasm_bindgen_test]
+async fn dispatch_events() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let record = |events: &Rc<RefCell<Vec<String>>>| {
+        let events = events.clone();
+        move |event: String| events.borrow_mut().push(event)
+    };
+
+    let app = div()
+        .on_click({
+            let record = record(&events);
+            move |_, _| record("click".to_string())
+        })
+        .on_keydown({
+            let record = record(&events);
+            move |ev, _| record(format!("keydown {}", ev.key()))
+        });
+    let text_input = input().on_input({
+        let record = record(&events);
+        move |_, input| record(format!("input {}", input.value()))
+    });
+    let text_input_handle = text_input.handle();
+    let app_handle = app.handle();
+
+    mount(APP_ID, app.child(text_input));
+    render_now().await;
+
+    assert!(app_handle.dispatch_click().unwrap());
+    assert!(app_handle.dispatch_keydown("Enter").unwrap());
+    assert!(text_input_handle.dispatch_input("abc").unwrap());
+    assert_eq!(
+        *events.borrow(),
+        ["click", "keydown Enter", "input abc"],
+        "Events are dispatched, with the value set before the input event"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn key_events() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let app = div()
+        .id(APP_ID)
+        .on_key(Key::Enter, {
+            let events = events.clone();
+            move |_, _| events.borrow_mut().push("enter")
+        })
+        .on_key_combo(Mods::CTRL, "s", {
+            let events = events.clone();
+            move |_, _| events.borrow_mut().push("save")
+        });
+    let app_handle = app.handle();
+
+    mount(APP_ID, app);
+    render_now().await;
+
+    let ctrl_key = |key: &str| {
+        web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+            "keydown",
+            web_sys::KeyboardEventInit::new()
+                .bubbles(true)
+                .key(key)
+                .ctrl_key(true),
+        )
+        .unwrap()
+    };
+
+    app_handle.dispatch_keydown("Enter").unwrap();
+    app_handle.dispatch_keydown("s").unwrap();
+    app_handle.dispatch_event(&ctrl_key("S")).unwrap();
+    app_handle.dispatch_event(&ctrl_key("a")).unwrap();
+    assert_eq!(*events.borrow(), ["enter", "save"]);
+}
+
+#[wasm_bindgen_test]
+async fn delegated_events() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let clicked = Rc::new(RefCell::new(Vec::new()));
+    let _callback = document::delegate("click", ".item", {
+        let clicked = clicked.clone();
+        move |_: web_sys::MouseEvent, item| clicked.borrow_mut().push(item.id())
+    });
+    let item_child = p();
+    let item_child_handle = item_child.handle();
+    let other = div();
+    let other_handle = other.handle();
+
+    mount(
+        APP_ID,
+        div()
+            .id(APP_ID)
+            .child(div().id("item").class("item").child(item_child))
+            .child(other),
+    );
+    render_now().await;
+
+    item_child_handle.dispatch_click().unwrap();
+    other_handle.dispatch_click().unwrap();
+    assert_eq!(
+        *clicked.borrow(),
+        ["item"],
+        "Events are passed the closest element matching the selector"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn once_event_listener() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let click_count = Rc::new(Cell::new(0));
+    let app = div().id(APP_ID).on_click_with_options(
+        EventListenerOptions {
+            once: true,
+            ..EventListenerOptions::default()
+        },
+        {
+            let click_count = click_count.clone();
+            move |_, _| click_count.set(click_count.get() + 1)
+        },
+    );
+    let app_handle = app.handle();
+
+    mount(APP_ID, app);
+    render_now().await;
+
+    app_handle.dispatch_click().unwrap();
+    app_handle.dispatch_click().unwrap();
+    assert_eq!(
+        click_count.get(),
+        1,
+        "`once` listeners are only called once"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn focus_without_scroll() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let text_input = input();
+    let text_input_handle = text_input.handle();
+
+    mount(
+        APP_ID,
+        div()
+            .id(APP_ID)
+            .child(div().style_property("height", "10000px"))
+            .child(text_input),
+    );
+    render_now().await;
+
+    let window = web_sys::window().unwrap();
+    let scroll_y = window.scroll_y().unwrap();
+    text_input_handle
+        .focus_with_options(FocusOptions {
+            prevent_scroll: true,
+            ..FocusOptions::default()
+        })
+        .unwrap();
+
+    let active_element = window.document().unwrap().active_element().unwrap();
+    assert_eq!(
+        &active_element,
+        AsRef::<web_sys::Element>::as_ref(&text_input_handle.dom_element())
+    );
+    assert_eq!(
+        window.scroll_y().unwrap(),
+        scroll_y,
+        "The input is below the fold, but the window isn't scrolled"
+    );
+}
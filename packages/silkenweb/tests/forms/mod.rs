@@ -0,0 +1,228 @@
+use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use silkenweb::{
+    elements::html::{div, input, select},
+    mount,
+    node::element::ParentElement,
+    prelude::HtmlElement,
+    task::{flush, render_now},
+};
+use silkenweb_test::{click, type_text, BrowserTest};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::{HtmlInputElement, HtmlOptionElement, HtmlSelectElement};
+
+use crate::APP_ID;
+
+#[wasm_bindgen_test]
+async fn value_bindings() {
+    let test = BrowserTest::new(APP_ID).await;
+    let text = Mutable::new("initial".to_string());
+    let checked = Mutable::new(false);
+
+    mount(
+        APP_ID,
+        div()
+            .child(input().id("text").value_signal_in(&text))
+            .child(
+                input()
+                    .id("checkbox")
+                    .r#type("checkbox")
+                    .checked_signal_in(&checked),
+            ),
+    );
+    render_now().await;
+
+    let text_input: HtmlInputElement = test.query("#text").unwrap().unchecked_into();
+    assert_eq!(text_input.value(), "initial");
+
+    type_text(&text_input, "!").await;
+    assert_eq!(text.get_cloned(), "initial!");
+
+    text.set("updated".to_string());
+    render_now().await;
+    assert_eq!(text_input.value(), "updated");
+
+    let checkbox: HtmlInputElement = test.query("#checkbox").unwrap().unchecked_into();
+    click(&checkbox).await;
+    assert!(checked.get());
+
+    checked.set(false);
+    render_now().await;
+    assert!(!checkbox.checked());
+}
+
+#[wasm_bindgen_test]
+async fn parsed_value_binding() {
+    let test = BrowserTest::new(APP_ID).await;
+    let number = Mutable::new(Ok(0.0));
+
+    mount(
+        APP_ID,
+        input()
+            .id("number")
+            .r#type("number")
+            .bind_parsed::<f64>(&number),
+    );
+    render_now().await;
+
+    let number_input: HtmlInputElement = test.query("#number").unwrap().unchecked_into();
+    assert_eq!(number_input.value(), "0");
+
+    number_input.set_value("");
+    type_text(&number_input, "1.5").await;
+    assert_eq!(*number.lock_ref(), Ok(1.5));
+
+    number.set(Ok(2.25));
+    render_now().await;
+    assert_eq!(number_input.value(), "2.25");
+
+    number_input.set_value("");
+    number_input
+        .dispatch_event(&web_sys::Event::new("input").unwrap())
+        .unwrap();
+    assert!(number.lock_ref().is_err());
+    assert_eq!(number_input.value(), "", "Errors don't change the value");
+}
+
+#[wasm_bindgen_test]
+async fn select_bindings() {
+    let test = BrowserTest::new(APP_ID).await;
+    let options = MutableVec::new_with_values(vec![
+        ("a".to_string(), "A".to_string()),
+        ("b".to_string(), "B".to_string()),
+        ("c".to_string(), "C".to_string()),
+    ]);
+    let selected = Mutable::new("b".to_string());
+    let selected_many = Mutable::new(vec!["a".to_string(), "c".to_string()]);
+
+    mount(
+        APP_ID,
+        div()
+            .child(
+                select()
+                    .id("single")
+                    .options_signal(options.signal_vec_cloned())
+                    .value_signal_in(&selected),
+            )
+            .child(
+                select()
+                    .id("many")
+                    .multiple(true)
+                    .options_signal(options.signal_vec_cloned())
+                    .selected_many(&selected_many),
+            ),
+    );
+    render_now().await;
+
+    let single: HtmlSelectElement = test.query("#single").unwrap().unchecked_into();
+    assert_eq!(single.length(), 3);
+    assert_eq!(single.value(), "b");
+
+    selected.set("c".to_string());
+    render_now().await;
+    assert_eq!(single.value(), "c");
+
+    single.set_value("a");
+    single
+        .dispatch_event(&web_sys::Event::new("change").unwrap())
+        .unwrap();
+    assert_eq!(selected.get_cloned(), "a");
+
+    let many: HtmlSelectElement = test.query("#many").unwrap().unchecked_into();
+    assert_eq!(many.selected_index(), 0);
+    many.item(0)
+        .unwrap()
+        .unchecked_into::<HtmlOptionElement>()
+        .set_selected(false);
+    many.dispatch_event(&web_sys::Event::new("change").unwrap())
+        .unwrap();
+    assert_eq!(selected_many.get_cloned(), ["c"]);
+}
+
+#[wasm_bindgen_test]
+async fn select_bindings_reapply_after_options_change() {
+    let test = BrowserTest::new(APP_ID).await;
+    let options = MutableVec::new_with_values(vec![("a".to_string(), "A".to_string())]);
+    let selected = Mutable::new("b".to_string());
+    let selected_many = Mutable::new(vec!["a".to_string(), "b".to_string()]);
+
+    mount(
+        APP_ID,
+        div()
+            .child(
+                select()
+                    .id("single")
+                    .options_signal(options.signal_vec_cloned())
+                    .value_signal_in(&selected),
+            )
+            .child(
+                select()
+                    .id("many")
+                    .multiple(true)
+                    .options_signal(options.signal_vec_cloned())
+                    .selected_many(&selected_many),
+            ),
+    );
+    render_now().await;
+
+    let single: HtmlSelectElement = test.query("#single").unwrap().unchecked_into();
+    let many: HtmlSelectElement = test.query("#many").unwrap().unchecked_into();
+    assert_ne!(single.value(), "b");
+
+    options
+        .lock_mut()
+        .push_cloned(("b".to_string(), "B".to_string()));
+    flush().await;
+
+    assert_eq!(single.value(), "b");
+    assert!(many
+        .item(1)
+        .unwrap()
+        .unchecked_into::<HtmlOptionElement>()
+        .selected());
+}
+
+#[wasm_bindgen_test]
+async fn keyed_children_keep_input_state() {
+    let test = BrowserTest::new(APP_ID).await;
+    let keys = MutableVec::new_with_values(vec![0, 1, 2]);
+
+    mount(
+        APP_ID,
+        div().id(APP_ID).children_signal_keyed(
+            keys.signal_vec(),
+            |key| *key,
+            |key| input().id(format!("input-{key}")),
+        ),
+    );
+    render_now().await;
+
+    let focused: HtmlInputElement = test.query("#input-1").unwrap().unchecked_into();
+    focused.focus().unwrap();
+    type_text(&focused, "abc").await;
+
+    // Only `2` needs to move, so the focused input stays where it is.
+    keys.lock_mut().replace_cloned(vec![2, 0, 1]);
+    render_now().await;
+
+    assert_eq!(
+        test.html(),
+        r#"<div id="app"><input id="input-2"><input id="input-0"><input id="input-1"></div>"#
+    );
+
+    let active_element = web_sys::window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .active_element()
+        .unwrap();
+    assert_eq!(&active_element, AsRef::<web_sys::Element>::as_ref(&focused));
+    assert_eq!(
+        test.query("#input-1")
+            .unwrap()
+            .unchecked_into::<HtmlInputElement>()
+            .value(),
+        "abc",
+        "The input is reused, so it keeps its value"
+    );
+}
@@ -1,11 +1,12 @@
-use futures_signals::signal::Mutable;
+use futures::StreamExt;
+use futures_signals::signal::{Mutable, SignalExt};
 use silkenweb::{
     dom::Hydro,
     elements::{
         html::{self, button, div, p},
         ElementEvents, HtmlElement,
     },
-    hydration::hydrate,
+    hydration::{hydrate, hydrate_islands},
     node::element::{Const, GenericElement, ShadowRootParent},
     prelude::{Element, ParentElement},
     task::render_now,
@@ -76,6 +77,56 @@ async fn extra_child() {
     .await;
 }
 
+#[wasm_bindgen_test]
+async fn mismatch_report() {
+    let _test = app_container(APP_ID, r#"<p class="old">Goodbye</p><div></div>"#).await;
+    let app = div().id(APP_ID).child(p().text("Hello"));
+
+    render_now().await;
+    let stats = hydrate(APP_ID, app).await;
+    let mismatches: Vec<String> = stats.mismatches().iter().map(|m| m.to_string()).collect();
+    assert_eq!(
+        mismatches,
+        [
+            "div#app > p:nth-child(1): attribute `class` removed",
+            r#"div#app > p:nth-child(1) > #text: expected text "Hello", found "Goodbye""#,
+            "div#app > p:nth-child(1) > #text: node removed",
+            "div#app > div:nth-child(2): node removed",
+        ]
+    );
+    assert!(!stats.only_whitespace_diffs());
+}
+
+#[wasm_bindgen_test]
+async fn islands() {
+    type Island = Box<dyn FnOnce() -> GenericElement<Hydro, Const>>;
+
+    let test = app_container(
+        APP_ID,
+        r#"<div id="island-1"><p>One</p></div><p>Static</p><div id="island-2"></div>"#,
+    )
+    .await;
+
+    let stats = hydrate_islands([
+        (
+            "island-1",
+            Box::new(|| div().id("island-1").child(p().text("One")).into()) as Island,
+        ),
+        (
+            "island-2",
+            Box::new(|| div().id("island-2").child(p().text("Two")).into()) as Island,
+        ),
+    ])
+    .await;
+
+    assert!(stats["island-1"].exact_match());
+    assert_eq!(stats["island-2"].nodes_added(), 1);
+    assert_eq!(
+        test.html(),
+        r#"<div id="app"><div id="island-1"><p>One</p></div><p>Static</p><div id="island-2"><p>Two</p></div></div>"#
+    );
+}
+
 #[wasm_bindgen_test]
 async fn mismatched_mount_point_tag() {
     let test = app_container(
@@ -310,6 +361,20 @@ async fn shadow_root_hydration() {
     );
 }
 
+#[wasm_bindgen_test]
+async fn dom_element_signal() {
+    let _test = app_container(APP_ID, r#"<p data-silkenweb="1">Hello, world!</p>"#).await;
+    let text = p().text("Hello, world!");
+    let dom_element = text.handle().dom_element_signal().broadcast();
+    let is_hydrated = || dom_element.signal_ref(Option::is_some).to_stream().next();
+    let app = div().id(APP_ID).child(text);
+
+    assert_eq!(is_hydrated().await, Some(false));
+    hydrate(APP_ID, app).await;
+    render_now().await;
+    assert_eq!(is_hydrated().await, Some(true));
+}
+
 async fn app_container(id: &str, inner_html: &str) -> BrowserTest {
     let test = BrowserTest::new(id).await;
     html_element(id).set_inner_html(inner_html);
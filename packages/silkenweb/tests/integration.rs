@@ -31,9 +31,17 @@ macro_rules! isomorphic_test {
 mod children;
 mod component;
 mod css;
+mod document;
 mod element;
+mod events;
+mod forms;
 mod hydration;
+mod observers;
+mod task;
 mod template;
+mod time;
+mod transition;
+mod web_component;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -0,0 +1,86 @@
+//! Browser benchmarks for string interning.
+//!
+//! Interning only has an effect on [`Wet`] elements, when wasm-bindgen's
+//! `enable-interning` feature is on, so these time element creation in the
+//! browser instead of using criterion. Run them with
+//! `wasm-pack test --release --headless --chrome -- --test interning`, and the
+//! timings are logged to the console.
+//!
+//! They're in their own test binary, as they fill the dynamic interning cache,
+//! which would affect other tests.
+//!
+//! [`Wet`]: silkenweb::dom::Wet
+#[silkenweb_macros::cfg_browser(true)]
+mod browser {
+    use std::hint::black_box;
+
+    use silkenweb::{
+        class_set,
+        dom::Wet,
+        elements::html::{div, Div},
+        node::element::Element,
+    };
+    use wasm_bindgen_test::{console_log, wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const ELEMENT_COUNT: usize = 1000;
+    const CLASSES: [&str; 4] = ["button", "primary", "large", "rounded"];
+    const RUNS: u32 = 20;
+
+    /// More than the number of dynamic strings that are interned.
+    const CACHE_FILL_COUNT: usize = 2048;
+
+    /// Log the mean time of `RUNS` runs of `f`, after a warm up run.
+    fn bench(name: &str, mut f: impl FnMut()) {
+        let performance = web_sys::window().unwrap().performance().unwrap();
+        f();
+
+        let start = performance.now();
+
+        for _ in 0..RUNS {
+            f();
+        }
+
+        let mean = (performance.now() - start) / f64::from(RUNS);
+        console_log!("{name}: {mean:.3}ms");
+    }
+
+    fn data_attributes(name: &str) {
+        for i in 0..ELEMENT_COUNT {
+            let elem: Div<Wet> = div().attribute(name, i.to_string());
+            black_box(elem);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn attributes() {
+        bench("attributes/interned", || data_attributes("data-row"));
+
+        // Once the cache is full, new names aren't interned.
+        for i in 0..CACHE_FILL_COUNT {
+            let elem: Div<Wet> = div().attribute(&format!("data-fill-{i}"), "");
+            black_box(elem);
+        }
+
+        bench("attributes/uninterned", || data_attributes("data-column"));
+    }
+
+    #[wasm_bindgen_test]
+    fn classes() {
+        bench("classes/array", || {
+            for _ in 0..ELEMENT_COUNT {
+                let elem: Div<Wet> = div().classes(CLASSES);
+                black_box(elem);
+            }
+        });
+
+        bench("classes/class_set", || {
+            for _ in 0..ELEMENT_COUNT {
+                let elem: Div<Wet> =
+                    div().classes(class_set!["button", "primary", "large", "rounded"]);
+                black_box(elem);
+            }
+        });
+    }
+}
@@ -0,0 +1,190 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use futures_signals::signal::SignalExt;
+use js_sys::{Function, Reflect};
+use silkenweb::{
+    elements::html::div,
+    mount,
+    node::element::{AnchorSide, Element},
+    prelude::HtmlElement,
+    task::{render_now, spawn_local},
+    time::sleep,
+};
+use silkenweb_base::document::create_element;
+use silkenweb_test::BrowserTest;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::APP_ID;
+
+#[wasm_bindgen_test]
+async fn observe_children_debounced() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let app = div().id(APP_ID);
+    let app_handle = app.handle();
+    let ticks = Rc::new(Cell::new(0));
+
+    spawn_local(
+        app_handle
+            .observe_children_debounced(Duration::from_millis(100))
+            .for_each({
+                let ticks = ticks.clone();
+                move |()| {
+                    ticks.set(ticks.get() + 1);
+                    async {}
+                }
+            }),
+    );
+    mount(APP_ID, app);
+    render_now().await;
+
+    let app_elem = app_handle.dom_element();
+
+    for _ in 0..3 {
+        app_elem.append_child(&create_element("p")).unwrap();
+    }
+
+    sleep(Duration::from_millis(20)).await;
+    app_elem.append_child(&create_element("p")).unwrap();
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(ticks.get(), 0, "Changes are still settling");
+
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(ticks.get(), 1, "A burst of changes is a single tick");
+}
+
+#[wasm_bindgen_test]
+async fn on_resize() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let size = Rc::new(Cell::new(None));
+    let app = div()
+        .id(APP_ID)
+        .style_property("width", "100px")
+        .style_property("height", "50px")
+        .on_resize({
+            let size = size.clone();
+            move |width, height| size.set(Some((width, height)))
+        });
+    let app_handle = app.handle();
+
+    mount(APP_ID, app);
+    render_now().await;
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(size.get(), Some((100.0, 50.0)));
+
+    resize(&app_handle.dom_element(), "200px");
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(size.get(), Some((200.0, 50.0)));
+}
+
+fn resize(elem: &web_sys::HtmlElement, width: &str) {
+    elem.style().set_property("width", width).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn visible_signal() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let app = div().id(APP_ID).text("Visible");
+    let app_handle = app.handle();
+    let is_visible = Rc::new(Cell::new(None));
+
+    spawn_local(app_handle.visible_signal().for_each({
+        let is_visible = is_visible.clone();
+        move |visible| {
+            is_visible.set(Some(visible));
+            async {}
+        }
+    }));
+
+    mount(APP_ID, app);
+    render_now().await;
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(is_visible.get(), Some(true));
+
+    app_handle
+        .dom_element()
+        .style()
+        .set_property("display", "none")
+        .unwrap();
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(is_visible.get(), Some(false));
+}
+
+#[wasm_bindgen_test]
+async fn size_signal() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let app = div()
+        .id(APP_ID)
+        .style_property("width", "100px")
+        .style_property("height", "50px");
+    let app_handle = app.handle();
+    let size = Rc::new(Cell::new((0.0, 0.0)));
+
+    spawn_local(app_handle.size_signal().for_each({
+        let size = size.clone();
+        move |new_size| {
+            size.set(new_size);
+            async {}
+        }
+    }));
+
+    mount(APP_ID, app);
+    render_now().await;
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(size.get(), (100.0, 50.0));
+
+    resize(&app_handle.dom_element(), "200px");
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(size.get(), (200.0, 50.0));
+}
+
+#[wasm_bindgen_test]
+async fn position_anchor_fallback() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let _unsupported = NoAnchorPositioning::new();
+    let anchor = div()
+        .anchor_name("--anchor")
+        .style_property("position", "absolute")
+        .style_property("top", "100px")
+        .style_property("left", "20px")
+        .style_property("width", "50px")
+        .style_property("height", "30px");
+    let popup = div().position_anchor("--anchor", anchor.handle(), AnchorSide::Bottom);
+    let popup_handle = popup.handle();
+
+    mount(APP_ID, div().id(APP_ID).child(anchor).child(popup));
+    render_now().await;
+    sleep(Duration::from_millis(50)).await;
+
+    let style = popup_handle.dom_element().style();
+    assert_eq!(style.get_property_value("top").unwrap(), "130px");
+    assert_eq!(style.get_property_value("left").unwrap(), "20px");
+}
+
+/// Make `CSS.supports` return `false` until dropped, so anchor positioning
+/// falls back to JavaScript.
+struct NoAnchorPositioning {
+    css: JsValue,
+    supports: JsValue,
+}
+
+impl NoAnchorPositioning {
+    fn new() -> Self {
+        let css = Reflect::get(&js_sys::global(), &"CSS".into()).unwrap();
+        let supports = Reflect::get(&css, &"supports".into()).unwrap();
+        Reflect::set(
+            &css,
+            &"supports".into(),
+            &Function::new_no_args("return false"),
+        )
+        .unwrap();
+
+        Self { css, supports }
+    }
+}
+
+impl Drop for NoAnchorPositioning {
+    fn drop(&mut self) {
+        Reflect::set(&self.css, &"supports".into(), &self.supports).unwrap();
+    }
+}
@@ -0,0 +1,35 @@
+use std::{cell::Cell, rc::Rc};
+
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::task::{render_now, spawn_tied_to, TaskOwner};
+
+isomorphic_test! {
+    async fn spawn_tied_to_owner() {
+        let count = Mutable::new(0);
+        let seen = Rc::new(Cell::new(0));
+        let owner = TaskOwner::new();
+
+        spawn_tied_to(&owner, count.signal().for_each({
+            let seen = seen.clone();
+            move |count| {
+                seen.set(count);
+                async {}
+            }
+        }));
+
+        count.set(1);
+        render_now().await;
+        assert_eq!(seen.get(), 1);
+
+        let owner_clone = owner.clone();
+        drop(owner);
+        count.set(2);
+        render_now().await;
+        assert_eq!(seen.get(), 2, "Future is running while a clone is alive");
+
+        drop(owner_clone);
+        count.set(3);
+        render_now().await;
+        assert_eq!(seen.get(), 2, "Future is cancelled with the last clone");
+    }
+}
@@ -49,6 +49,17 @@ isomorphic_test! {
     }
 }
 
+isomorphic_test! {
+    async fn template_optional_child() {
+        let template: Div<Template<bool>, Const> = div()
+            .child(div().id("always"))
+            .child_on_instantiate(|show: &bool| show.then(|| div().id("badge")))
+            .freeze();
+        check(&template, true, r#"<div><div id="always"></div><div id="badge"></div></div>"#).await;
+        check(&template, false, r#"<div><div id="always"></div></div>"#).await;
+    }
+}
+
 isomorphic_test! {
     async fn template_text_signal() {
         let text = Mutable::new("Hello, world!".to_string());
@@ -0,0 +1,192 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::{
+    task::{spawn_local, spawn_tied_to, TaskOwner},
+    time::{debounce, throttle},
+};
+use silkenweb_macros::cfg_browser;
+
+fn collect_throttled(source: &Mutable<i32>, interval: Duration) -> Rc<RefCell<Vec<i32>>> {
+    let values = Rc::new(RefCell::new(Vec::new()));
+
+    spawn_local(throttle(source.signal(), interval).for_each({
+        let values = values.clone();
+
+        move |value| {
+            values.borrow_mut().push(value);
+            async {}
+        }
+    }));
+
+    values
+}
+
+#[cfg_browser(false)]
+#[test]
+fn throttle_on_server() {
+    use silkenweb::task::{
+        render_now,
+        server::{block_on, scope},
+    };
+
+    block_on(scope(async {
+        let source = Mutable::new(0);
+        let values = collect_throttled(&source, Duration::from_millis(100));
+        render_now().await;
+
+        for value in 1..=3 {
+            source.set(value);
+            render_now().await;
+        }
+
+        assert_eq!(*values.borrow(), [0, 1, 2, 3]);
+    }))
+}
+
+#[cfg_browser(true)]
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn throttle_interval() {
+    use silkenweb::time::sleep;
+
+    let source = Mutable::new(0);
+    let values = collect_throttled(&source, Duration::from_millis(100));
+
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(*values.borrow(), [0], "First value is immediate");
+
+    source.set(1);
+    source.set(2);
+    sleep(Duration::from_millis(20)).await;
+    source.set(3);
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        *values.borrow(),
+        [0],
+        "Values are held until the interval ends"
+    );
+
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(*values.borrow(), [0, 3], "Only the latest value is yielded");
+
+    source.set(4);
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(*values.borrow(), [0, 3]);
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(*values.borrow(), [0, 3, 4]);
+
+    sleep(Duration::from_millis(200)).await;
+    source.set(5);
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        *values.borrow(),
+        [0, 3, 4, 5],
+        "A value is immediate after a quiet interval"
+    );
+}
+
+fn collect_debounced(
+    source: &Mutable<i32>,
+    duration: Duration,
+) -> (Rc<RefCell<Vec<i32>>>, TaskOwner) {
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let owner = TaskOwner::new();
+
+    spawn_tied_to(
+        &owner,
+        debounce(source.signal(), duration).for_each({
+            let values = values.clone();
+
+            move |value| {
+                values.borrow_mut().push(value);
+                async {}
+            }
+        }),
+    );
+
+    (values, owner)
+}
+
+#[cfg_browser(false)]
+#[test]
+fn debounce_on_server() {
+    use silkenweb::task::{
+        render_now,
+        server::{block_on, scope},
+    };
+
+    block_on(scope(async {
+        let source = Mutable::new(0);
+        let (values, _owner) = collect_debounced(&source, Duration::from_millis(100));
+        render_now().await;
+
+        for value in 1..=3 {
+            source.set(value);
+            render_now().await;
+        }
+
+        assert_eq!(*values.borrow(), [0, 1, 2, 3]);
+    }))
+}
+
+#[cfg_browser(true)]
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn debounce_burst() {
+    use silkenweb::time::sleep;
+
+    let source = Mutable::new(0);
+    let (values, _owner) = collect_debounced(&source, Duration::from_millis(100));
+
+    sleep(Duration::from_millis(20)).await;
+    assert!(
+        values.borrow().is_empty(),
+        "The first value waits for the signal to be quiet"
+    );
+    sleep(Duration::from_millis(150)).await;
+    assert_eq!(*values.borrow(), [0]);
+
+    source.set(1);
+    source.set(2);
+    sleep(Duration::from_millis(20)).await;
+    source.set(3);
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(*values.borrow(), [0], "Values are held during a burst");
+
+    sleep(Duration::from_millis(150)).await;
+    assert_eq!(*values.borrow(), [0, 3], "Only the last value is yielded");
+}
+
+#[cfg_browser(true)]
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn debounce_source_ends() {
+    use silkenweb::time::sleep;
+
+    let source = Mutable::new(0);
+    let (values, _owner) = collect_debounced(&source, Duration::from_millis(100));
+
+    source.set(1);
+    drop(source);
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        *values.borrow(),
+        [1],
+        "The waiting value is yielded when the signal ends"
+    );
+}
+
+#[cfg_browser(true)]
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn debounce_dropped() {
+    use silkenweb::time::sleep;
+
+    let source = Mutable::new(0);
+    let (values, owner) = collect_debounced(&source, Duration::from_millis(100));
+
+    sleep(Duration::from_millis(20)).await;
+    drop(owner);
+    sleep(Duration::from_millis(150)).await;
+    assert!(
+        values.borrow().is_empty(),
+        "Nothing is yielded once the debounced signal is dropped"
+    );
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::{
+    document::Document,
+    dom::DefaultDom,
+    elements::html::{div, p, style},
+    mount,
+    node::element::{Element, ParentElement},
+    prelude::HtmlElement,
+    task::render_now,
+    time::sleep,
+};
+use silkenweb_macros::cfg_browser;
+use silkenweb_test::BrowserTest;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::APP_ID;
+
+const STYLE: &str = "
+    .tab { opacity: 1; }
+    .fade.tab-leave { transition: opacity 50ms; opacity: 0; }
+    .hidden { display: none; }
+";
+
+#[cfg_browser(false)]
+#[test]
+fn transition_without_dom() {
+    use silkenweb::{
+        node::Node,
+        task::{
+            flush,
+            server::{block_on, scope},
+        },
+    };
+
+    block_on(scope(async {
+        let tab = Mutable::new(0);
+        let elem: Node = div()
+            .child_signal_with_transition(
+                tab.signal().map(|tab| p().text(format!("{tab}"))),
+                "tab-enter",
+                "tab-leave",
+            )
+            .into();
+
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><p>0</p></div>");
+
+        tab.set(1);
+        flush().await;
+        assert_eq!(
+            elem.to_string(),
+            "<div><p>1</p></div>",
+            "The old child is removed straight away"
+        );
+    }))
+}
+
+#[wasm_bindgen_test]
+async fn transition_end() {
+    let test = BrowserTest::new(APP_ID).await;
+    let tab = Mutable::new(0);
+    mount_tabs(&tab, &["fade"]).await;
+
+    tab.set(1);
+    render_now().await;
+    let html = test.html();
+    assert!(
+        html.contains(r#"<p class="tab fade tab-leave">0</p>"#),
+        "The old child is present during the transition"
+    );
+    assert!(html.contains(">1</p>"));
+
+    sleep(Duration::from_millis(200)).await;
+    assert!(
+        !test.html().contains(">0</p>"),
+        "The old child is removed when the transition ends"
+    );
+    assert!(test.html().contains(">1</p>"));
+}
+
+#[wasm_bindgen_test]
+async fn transition_timeout() {
+    let test = BrowserTest::new(APP_ID).await;
+    let tab = Mutable::new(0);
+    // Hidden elements don't run transitions, so `transitionend` never fires.
+    mount_tabs(&tab, &["fade", "hidden"]).await;
+
+    tab.set(1);
+    render_now().await;
+    assert!(test.html().contains(">0</p>"));
+
+    sleep(Duration::from_millis(200)).await;
+    assert!(
+        !test.html().contains(">0</p>"),
+        "The old child is removed after the transition duration"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn transition_without_duration() {
+    let test = BrowserTest::new(APP_ID).await;
+    let tab = Mutable::new(0);
+    mount_tabs(&tab, &[]).await;
+
+    tab.set(1);
+    sleep(Duration::from_millis(100)).await;
+    assert!(
+        !test.html().contains(">0</p>"),
+        "The old child is removed when there's no transition"
+    );
+}
+
+async fn mount_tabs(tab: &Mutable<usize>, classes: &'static [&'static str]) {
+    DefaultDom::mount_in_head("transition-style", style().text(STYLE));
+
+    mount(
+        APP_ID,
+        div().id(APP_ID).child_signal_with_transition(
+            tab.signal()
+                .map(move |tab| p().class("tab").classes(classes).text(format!("{tab}"))),
+            "tab-enter",
+            "tab-leave",
+        ),
+    );
+    render_now().await;
+}
@@ -0,0 +1,100 @@
+use std::{cell::Cell, time::Duration};
+
+use futures_signals::signal::SignalExt;
+use silkenweb::{
+    define_web_component,
+    dom::Wet,
+    elements::html::div,
+    node::element::{Const, GenericElement},
+    prelude::{Element, ParentElement},
+    task::render_now,
+    time::sleep,
+    value::Sig,
+    web_component::{Attributes, WebComponent},
+};
+use silkenweb_base::document;
+use silkenweb_test::{html_element, BrowserTest};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::APP_ID;
+
+struct Greeting;
+
+impl WebComponent for Greeting {
+    fn render(attributes: &Attributes) -> GenericElement<Wet, Const> {
+        div()
+            .text(Sig(attributes.signal("name").map(|name| {
+                format!("Hello, {}!", name.as_deref().unwrap_or("world"))
+            })))
+            .into()
+    }
+}
+
+#[wasm_bindgen_test]
+async fn connect_and_disconnect() {
+    let _test = BrowserTest::new(APP_ID).await;
+    define_web_component!("silkenweb-greeting", Greeting, ["name"]).unwrap();
+
+    let host = document::create_element("silkenweb-greeting");
+    html_element(APP_ID).append_child(&host).unwrap();
+    render_now().await;
+
+    let shadow_root = host.shadow_root().unwrap();
+    assert_eq!(shadow_root.inner_html(), "<div>Hello, world!</div>");
+
+    host.set_attribute("name", "Alice").unwrap();
+    render_now().await;
+    assert_eq!(shadow_root.inner_html(), "<div>Hello, Alice!</div>");
+
+    host.remove();
+    assert_eq!(shadow_root.inner_html(), "");
+
+    host.set_attribute("name", "Bob").unwrap();
+    html_element(APP_ID).append_child(&host).unwrap();
+    render_now().await;
+    assert_eq!(shadow_root.inner_html(), "<div>Hello, Bob!</div>");
+}
+
+struct Ticker;
+
+thread_local! {
+    static TICKS: Cell<usize> = Cell::new(0);
+}
+
+impl WebComponent for Ticker {
+    fn render(_attributes: &Attributes) -> GenericElement<Wet, Const> {
+        div()
+            .text("Ticking")
+            .spawn_future(async {
+                loop {
+                    sleep(Duration::from_millis(10)).await;
+                    TICKS.with(|ticks| ticks.set(ticks.get() + 1));
+                }
+            })
+            .into()
+    }
+}
+
+#[wasm_bindgen_test]
+async fn reconnect() {
+    let _test = BrowserTest::new(APP_ID).await;
+    define_web_component!("silkenweb-ticker", Ticker).unwrap();
+
+    let host = document::create_element("silkenweb-ticker");
+    let other_parent = document::create_element("div");
+    html_element(APP_ID).append_child(&other_parent).unwrap();
+
+    for _ in 0..3 {
+        html_element(APP_ID).append_child(&host).unwrap();
+        other_parent.append_child(&host).unwrap();
+    }
+
+    let shadow_root = host.shadow_root().unwrap();
+    render_now().await;
+    assert_eq!(shadow_root.inner_html(), "<div>Ticking</div>");
+
+    host.remove();
+    let ticks = TICKS.with(Cell::get);
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(TICKS.with(Cell::get), ticks, "Futures are cancelled");
+}
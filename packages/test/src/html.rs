@@ -0,0 +1,335 @@
+//! Normalize and compare HTML for [`assert_html_eq!`].
+//!
+//! This isn't a full HTML parser. It handles the HTML that Silkenweb
+//! generates, and hand written HTML to compare it with.
+//!
+//! [`assert_html_eq!`]: crate::assert_html_eq
+use std::fmt::Write;
+
+use silkenweb::{dom::Dom, node::Node};
+
+/// Convert `element` into a [`Node`], so the [`Dom`] type can be inferred.
+pub fn node<D: Dom>(element: impl Into<Node<D>>) -> Node<D> {
+    element.into()
+}
+
+/// Compare `actual` and `expected` HTML, ignoring formatting whitespace and
+/// the order of attributes.
+///
+/// # Panics
+///
+/// If the HTML differs, with a line by line diff of the normalized HTML.
+pub fn assert_html_eq(actual: &str, expected: &str) {
+    let actual = normalize(actual);
+    let expected = normalize(expected);
+
+    if actual != expected {
+        panic!(
+            "HTML differs (-expected, +actual):\n{}",
+            diff(&expected, &actual)
+        );
+    }
+}
+
+/// Put each tag and text node on its own, indented, line.
+///
+/// Attributes are sorted by name. Whitespace at the start or end of a text
+/// node is removed if it contains a line break, as it's only there to format
+/// the HTML. Other runs of whitespace are collapsed to a single space, so
+/// `<b>a</b> <i>b</i>` keeps the space between the elements.
+fn normalize(html: &str) -> String {
+    let mut normalized = String::new();
+    let mut depth: usize = 0;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let token;
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(comment.len(), |end| end + 3);
+            token = Token::Other(&rest[..end + 4]);
+            rest = &comment[end..];
+        } else if rest.starts_with('<') {
+            let end = tag_end(rest);
+            token = parse_tag(&rest[..end]);
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            token = Token::Text(&rest[..end]);
+            rest = &rest[end..];
+        }
+
+        let is_open = matches!(token, Token::Open { .. });
+        let line = match token {
+            Token::Open { name, attributes } => {
+                let line = open_tag(name, attributes, "");
+                depth += 1;
+                line
+            }
+            Token::SelfClosing { name, attributes } => open_tag(name, attributes, "/"),
+            Token::Close(name) => {
+                depth = depth.saturating_sub(1);
+                format!("</{name}>")
+            }
+            Token::Text(text) => {
+                let text = normalize_text(text);
+
+                if text.is_empty() {
+                    continue;
+                }
+
+                text
+            }
+            Token::Other(other) => other.to_string(),
+        };
+
+        let indent = if is_open { depth - 1 } else { depth };
+
+        writeln!(normalized, "{:indent$}{line}", "", indent = indent * 2).unwrap();
+    }
+
+    normalized
+}
+
+fn normalize_text(text: &str) -> String {
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let text = if leading.contains('\n') {
+        text.trim_start()
+    } else {
+        text
+    };
+
+    let trailing = &text[text.trim_end().len()..];
+    let text = if trailing.contains('\n') {
+        text.trim_end()
+    } else {
+        text
+    };
+
+    let mut normalized = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !in_whitespace {
+                normalized.push(' ');
+            }
+
+            in_whitespace = true;
+        } else {
+            normalized.push(c);
+            in_whitespace = false;
+        }
+    }
+
+    normalized
+}
+
+enum Token<'a> {
+    Open {
+        name: &'a str,
+        attributes: Vec<(&'a str, &'a str)>,
+    },
+    SelfClosing {
+        name: &'a str,
+        attributes: Vec<(&'a str, &'a str)>,
+    },
+    Close(&'a str),
+    Text(&'a str),
+    Other(&'a str),
+}
+
+/// Find the end of the tag at the start of `html`, skipping over `>`s in
+/// quoted attribute values.
+fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+
+    for (index, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return index + 1,
+            _ => (),
+        }
+    }
+
+    html.len()
+}
+
+fn parse_tag(tag: &str) -> Token {
+    let inner = tag.trim_start_matches('<').trim_end_matches('>');
+
+    if let Some(name) = inner.strip_prefix('/') {
+        return Token::Close(name.trim());
+    }
+
+    if inner.starts_with('!') || inner.starts_with('?') {
+        return Token::Other(tag);
+    }
+
+    let (inner, self_closing) = match inner.strip_suffix('/') {
+        Some(inner) => (inner, true),
+        None => (inner, false),
+    };
+    let name_end = inner
+        .find(|c: char| c.is_ascii_whitespace())
+        .unwrap_or(inner.len());
+    let name = &inner[..name_end];
+    let attributes = parse_attributes(&inner[name_end..]);
+
+    if self_closing || VOID_ELEMENTS.contains(&name.to_ascii_lowercase().as_str()) {
+        Token::SelfClosing { name, attributes }
+    } else {
+        Token::Open { name, attributes }
+    }
+}
+
+fn parse_attributes(mut attributes: &str) -> Vec<(&str, &str)> {
+    let mut parsed = Vec::new();
+
+    loop {
+        attributes = attributes.trim_start();
+
+        if attributes.is_empty() {
+            break;
+        }
+
+        let name_end = attributes
+            .find(|c: char| c.is_ascii_whitespace() || c == '=')
+            .unwrap_or(attributes.len());
+        let name = &attributes[..name_end];
+        attributes = attributes[name_end..].trim_start();
+
+        let value = if let Some(value) = attributes.strip_prefix('=') {
+            let value = value.trim_start();
+
+            let (value, rest) = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value = &value[1..];
+                    let end = value.find(quote).unwrap_or(value.len());
+                    (&value[..end], value.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = value
+                        .find(|c: char| c.is_ascii_whitespace())
+                        .unwrap_or(value.len());
+                    (&value[..end], &value[end..])
+                }
+            };
+
+            attributes = rest;
+            value
+        } else {
+            // `hidden` is the same as `hidden=""`
+            ""
+        };
+
+        parsed.push((name, value));
+    }
+
+    parsed.sort_unstable();
+    parsed
+}
+
+fn open_tag(name: &str, attributes: Vec<(&str, &str)>, end: &str) -> String {
+    let mut tag = format!("<{name}");
+
+    for (name, value) in attributes {
+        write!(tag, r#" {name}="{}""#, value.replace('"', "&quot;")).unwrap();
+    }
+
+    tag.push_str(end);
+    tag.push('>');
+    tag
+}
+
+/// A line by line diff, using the longest common subsequence of lines.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // `lcs[i][j]` is the length of the longest common subsequence of
+    // `expected[i..]` and `actual[j..]`.
+    let mut lcs = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            writeln!(diff, " {}", expected[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            writeln!(diff, "-{}", expected[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(diff, "+{}", actual[j]).unwrap();
+            j += 1;
+        }
+    }
+
+    diff
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, normalize};
+
+    #[test]
+    fn normalize_formatting() {
+        assert_eq!(
+            normalize(
+                r#"
+                <div id="app" class="container">
+                    <p>Hello,   world!</p>
+                    <br>
+                </div>
+                "#
+            ),
+            "<div class=\"container\" id=\"app\">\n  <p>\n    Hello, world!\n  </p>\n  <br/>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_inline_whitespace() {
+        assert_eq!(
+            normalize("<p><b>a</b> <i>b</i></p>"),
+            "<p>\n  <b>\n    a\n  </b>\n   \n  <i>\n    b\n  </i>\n</p>\n"
+        );
+        assert_ne!(
+            normalize("<p><b>a</b> <i>b</i></p>"),
+            normalize("<p><b>a</b><i>b</i></p>")
+        );
+    }
+
+    #[test]
+    fn normalize_comments_and_quotes() {
+        assert_eq!(
+            normalize(r#"<!-- a > b --><input value='x > y' disabled>"#),
+            "<!-- a > b -->\n<input disabled=\"\" value=\"x > y\"/>\n"
+        );
+    }
+
+    #[test]
+    fn diff_lines() {
+        assert_eq!(diff("a\nb\nc\n", "a\nc\nd\n"), " a\n-b\n c\n+d\n");
+        assert_eq!(diff("a\n", "a\n"), " a\n");
+        assert_eq!(diff("", "a\n"), "+a\n");
+    }
+}
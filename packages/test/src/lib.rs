@@ -1,8 +1,93 @@
 //! Utilities for writing tests for Silkenweb apps.
-use silkenweb::{document::Document, dom::DefaultDom, task::render_now};
+//!
+//! Browser tests are run with [`wasm-bindgen-test`]. [`BrowserTest`] sets up a
+//! mount point for each test, and there are helpers to interact with the DOM
+//! like a user would, then wait for the results to render.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use silkenweb::{elements::html::{button, div, input, p}, mount, prelude::*};
+//! # use silkenweb_test::{click, type_text, BrowserTest};
+//! # async fn test() {
+//! let test = BrowserTest::new("app").await;
+//! let count = Mutable::new(0);
+//! let name = Mutable::new(String::new());
+//!
+//! mount(
+//!     "app",
+//!     div()
+//!         .child(button().id("increment").text("+").on_click({
+//!             let count = count.clone();
+//!             move |_, _| count.replace_with(|count| *count + 1)
+//!         }))
+//!         .child(input().id("name").on_input({
+//!             let name = name.clone();
+//!             move |_, input| name.set(input.value())
+//!         }))
+//!         .child(p().id("count").text(Sig(count.signal().map(|count| count.to_string())))),
+//! );
+//!
+//! click(&test.query("#increment").unwrap()).await;
+//! assert_eq!(test.query("#count").unwrap().inner_text(), "1");
+//!
+//! type_text(&test.query("#name").unwrap(), "Alice").await;
+//! assert_eq!(name.get_cloned(), "Alice");
+//! # }
+//! ```
+//!
+//! [`wasm-bindgen-test`]: https://rustwasm.github.io/wasm-bindgen/wasm-bindgen-test/index.html
+use silkenweb::{
+    document::Document,
+    dom::DefaultDom,
+    task::{flush, render_now},
+};
 use silkenweb_base::document;
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
+#[doc(hidden)]
+pub mod html;
+
+/// Assert that an element renders as `expected` HTML.
+///
+/// The order of attributes is ignored, and so is whitespace that includes a
+/// line break at the start or end of a text node, so `expected` can be
+/// indented. Other runs of whitespace are collapsed to a single space.
+/// `element` can be anything that converts into a [`Node`], and it's rendered
+/// with [`Display`]. If the HTML differs, this panics with a line by line diff.
+///
+/// # Example
+///
+/// ```
+/// # use silkenweb::{dom::Dry, elements::html::{div, p, Div}, prelude::*};
+/// # use silkenweb_test::assert_html_eq;
+/// let app: Div<Dry> = div()
+///     .id("app")
+///     .class("container")
+///     .child(p().text("Hello, world!"));
+///
+/// assert_html_eq!(
+///     app,
+///     r#"
+///     <div class="container" id="app">
+///         <p>Hello, world!</p>
+///     </div>
+///     "#
+/// );
+/// ```
+///
+/// [`Node`]: silkenweb::node::Node
+/// [`Display`]: std::fmt::Display
+#[macro_export]
+macro_rules! assert_html_eq {
+    ($element:expr, $expected:expr $(,)?) => {
+        $crate::html::assert_html_eq(
+            &::std::string::ToString::to_string(&$crate::html::node($element)),
+            $expected,
+        )
+    };
+}
+
 /// Setup a browser test.
 ///
 /// This will cleanup the test when `drop`ed.
@@ -48,6 +133,24 @@ impl BrowserTest {
     pub fn html(&self) -> String {
         html_element(APP_CONTAINER_ID).inner_html()
     }
+
+    /// Find the first element in the test that matches `selectors`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `selectors` is invalid, or the element is found and is
+    /// not an [`HtmlElement`].
+    ///
+    /// [`HtmlElement`]: web_sys::HtmlElement
+    pub fn query(&self, selectors: &str) -> Option<web_sys::HtmlElement> {
+        html_element(APP_CONTAINER_ID)
+            .query_selector(selectors)
+            .expect_throw("Invalid selector")
+            .map(|elem| {
+                elem.dyn_into()
+                    .expect_throw("Element was not an `HTMLElement`")
+            })
+    }
 }
 
 impl Drop for BrowserTest {
@@ -57,6 +160,47 @@ impl Drop for BrowserTest {
     }
 }
 
+/// Click `elem`, then render any updates.
+///
+/// This fires a `click` event, as if the user clicked the element, then waits
+/// for [`flush`] to render the results.
+pub async fn click(elem: &web_sys::HtmlElement) {
+    elem.click();
+    flush().await;
+}
+
+/// Type `text` into an `<input>` or `<textarea>`, then render any updates.
+///
+/// `text` is appended to the current value one character at a time, firing an
+/// `input` event for each, then the results are rendered with [`flush`].
+///
+/// # Panics
+///
+/// This panics if `elem` isn't an `<input>` or `<textarea>`.
+pub async fn type_text(elem: &web_sys::HtmlElement, text: &str) {
+    for c in text.chars() {
+        if let Some(input) = elem.dyn_ref::<web_sys::HtmlInputElement>() {
+            input.set_value(&format!("{}{c}", input.value()));
+        } else if let Some(text_area) = elem.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            text_area.set_value(&format!("{}{c}", text_area.value()));
+        } else {
+            panic!("Can only type into `<input>` or `<textarea>` elements")
+        }
+
+        let event = web_sys::InputEvent::new_with_event_init_dict(
+            "input",
+            web_sys::InputEventInit::new()
+                .bubbles(true)
+                .data(Some(&c.to_string())),
+        )
+        .expect_throw("Couldn't create input event");
+        elem.dispatch_event(&event)
+            .expect_throw("Couldn't dispatch input event");
+    }
+
+    flush().await;
+}
+
 /// Find an element by `id`
 ///
 /// # Panics